@@ -1,10 +1,11 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use shijim_indicators::metrics::vpin::DEFAULT_MAX_WINDOW_SIZE;
 use shijim_indicators::RustVpinCalculator;
 use shijim_indicators::RustHawkesIntensity;
 
 fn benchmark_vpin(c: &mut Criterion) {
     c.bench_function("vpin_update", |b| {
-        let mut calc = RustVpinCalculator::new(1000.0, 50).unwrap();
+        let mut calc = RustVpinCalculator::new(1000.0, 50, DEFAULT_MAX_WINDOW_SIZE, false).unwrap();
         let mut i = 0.0;
         b.iter(|| {
             i += 1.0;
@@ -16,7 +17,7 @@ fn benchmark_vpin(c: &mut Criterion) {
 
 fn benchmark_hawkes(c: &mut Criterion) {
     c.bench_function("hawkes_update", |b| {
-        let mut calc = RustHawkesIntensity::new(0.1, 0.5, 1.0).unwrap();
+        let mut calc = RustHawkesIntensity::new(0.1, 0.5, 1.0, false).unwrap();
         let mut t = 0.0;
         b.iter(|| {
             t += 0.001;
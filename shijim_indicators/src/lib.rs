@@ -1,14 +1,57 @@
+#![allow(non_local_definitions)]
+
 use pyo3::prelude::*;
 
+pub mod loaders;
 pub mod metrics;
+pub mod pipeline;
+pub mod protocols;
+pub use metrics::acd::RustAcdModel;
+pub use metrics::bars::{RustBarBuilder, RustBarSampler};
 pub use metrics::hawkes::RustHawkesIntensity;
-pub use metrics::ofi::RustOfiCalculator;
+pub use metrics::kyle_lambda::RustKyleLambda;
+pub use metrics::leadlag::RustHayashiYoshida;
+pub use metrics::lossy_count::RustLossyCounter;
+pub use metrics::microprice::RustMicroprice;
+pub use metrics::multivariate_hawkes::RustMultivariateHawkes;
+pub use metrics::ofi::{RustMultiLevelOfi, RustOfiCalculator};
+pub use metrics::order_book::RustOrderBook;
+pub use metrics::pairwise::RustRollingCorrelation;
+pub use metrics::queue::RustQueueEstimator;
+pub use metrics::rolling::RustRollingStats;
+pub use metrics::rv::RustRealizedVolatility;
+pub use metrics::spreads::RustSpreadMetrics;
+pub use metrics::trade_sign::RustTradeClassifier;
 pub use metrics::vpin::RustVpinCalculator;
+pub use metrics::vwap::RustVwapCalculator;
+pub use pipeline::RustPipeline;
 
 #[pymodule]
 fn shijim_indicators(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<RustOfiCalculator>()?;
     m.add_class::<RustVpinCalculator>()?;
     m.add_class::<RustHawkesIntensity>()?;
+    m.add_class::<RustBarBuilder>()?;
+    m.add_class::<RustLossyCounter>()?;
+    m.add_class::<RustOrderBook>()?;
+    m.add_class::<RustKyleLambda>()?;
+    m.add_class::<RustRealizedVolatility>()?;
+    m.add_class::<RustMultiLevelOfi>()?;
+    m.add_class::<RustMultivariateHawkes>()?;
+    m.add_class::<RustMicroprice>()?;
+    m.add_class::<RustTradeClassifier>()?;
+    m.add_class::<RustRollingStats>()?;
+    m.add_class::<RustAcdModel>()?;
+    m.add_class::<RustQueueEstimator>()?;
+    m.add_class::<RustSpreadMetrics>()?;
+    m.add_class::<RustBarSampler>()?;
+    m.add_class::<RustVwapCalculator>()?;
+    m.add_class::<RustPipeline>()?;
+    m.add_class::<RustRollingCorrelation>()?;
+    m.add_class::<RustHayashiYoshida>()?;
+    m.add_function(wrap_pyfunction!(loaders::parse_lobster_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(loaders::parse_itch_file, m)?)?;
+    m.add_function(wrap_pyfunction!(loaders::run_through_pipeline, m)?)?;
+    m.add_function(wrap_pyfunction!(loaders::run_itch_through_pipeline, m)?)?;
     Ok(())
 }
@@ -0,0 +1,452 @@
+use numpy::IntoPyArray;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind, Lines, Read};
+
+use crate::pipeline::{
+    Message, RustPipeline, EVENT_CLOSE, EVENT_HALT, EVENT_OPEN, EVENT_PRE_OPEN, EVENT_RESUME,
+};
+use crate::protocols::itch::{ItchDecoder, ItchEvent, ITCH_PRICE_SCALE};
+
+/// LOBSTER prices are integers in units of 1/10000 dollar.
+const LOBSTER_PRICE_SCALE: f64 = 10_000.0;
+/// Placeholder price LOBSTER writes for empty book levels.
+const LOBSTER_EMPTY_PRICE: f64 = 9_999_999_999.0;
+
+const EXECUTE_VISIBLE: i64 = 4;
+const EXECUTE_HIDDEN: i64 = 5;
+const TRADING_HALT: i64 = 7;
+
+/// Top of book as `(bid_price, bid_size, ask_price, ask_size)`.
+type TopOfBook = (f64, f64, f64, f64);
+
+struct LobsterRow {
+    time: f64,
+    event_type: i64,
+    order_id: i64,
+    size: f64,
+    price: f64,
+    direction: i64,
+    top: Option<TopOfBook>,
+}
+
+impl LobsterRow {
+    /// Normalized messages for this row: the execution (if any) happens against
+    /// the book before the event, then the resulting top of book is quoted.
+    fn messages(&self, last_top: &mut Option<TopOfBook>) -> Vec<Message> {
+        let mut out = Vec::with_capacity(2);
+        match self.event_type {
+            EXECUTE_VISIBLE | EXECUTE_HIDDEN => out.push(Message::Trade {
+                timestamp: self.time,
+                price: self.price,
+                volume: self.size,
+                // Direction is the side of the resting order that was hit.
+                sign: -(self.direction as f64),
+            }),
+            TRADING_HALT => {
+                // The price column is -1 (halt), 0 (quoting) or 1 (trading).
+                // Resumption stays within the session, so it never maps to
+                // EVENT_OPEN and rolls no indicators by default.
+                let event_code = if self.price < 0.0 {
+                    EVENT_HALT
+                } else if self.price == 0.0 {
+                    EVENT_PRE_OPEN
+                } else {
+                    EVENT_RESUME
+                };
+                out.push(Message::System {
+                    timestamp: self.time,
+                    event_code,
+                });
+            }
+            _ => {}
+        }
+        if let Some(top) = self.top {
+            if *last_top != Some(top) {
+                *last_top = Some(top);
+                let (bid_price, bid_size, ask_price, ask_size) = top;
+                if bid_size > 0.0 && ask_size > 0.0 {
+                    out.push(Message::Quote {
+                        timestamp: self.time,
+                        bid_price,
+                        bid_size,
+                        ask_price,
+                        ask_size,
+                    });
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Streams rows of a LOBSTER message file, joined with the matching
+/// orderbook file when one is given.
+struct LobsterReader {
+    messages: Lines<BufReader<File>>,
+    orderbook: Option<Lines<BufReader<File>>>,
+    price_scale: f64,
+    line: usize,
+}
+
+impl LobsterReader {
+    fn open(message_path: &str, orderbook_path: Option<&str>, price_scale: f64) -> PyResult<Self> {
+        if !price_scale.is_finite() || price_scale <= 0.0 {
+            return Err(PyValueError::new_err(
+                "price_scale must be a positive, finite number",
+            ));
+        }
+        let orderbook = match orderbook_path {
+            Some(path) => Some(Self::lines(path)?),
+            None => None,
+        };
+        Ok(Self {
+            messages: Self::lines(message_path)?,
+            orderbook,
+            price_scale,
+            line: 0,
+        })
+    }
+
+    fn lines(path: &str) -> PyResult<Lines<BufReader<File>>> {
+        let file = File::open(path)
+            .map_err(|err| PyIOError::new_err(format!("cannot open {path}: {err}")))?;
+        Ok(BufReader::new(file).lines())
+    }
+
+    fn next_row(&mut self) -> PyResult<Option<LobsterRow>> {
+        let Some(message) = self.messages.next() else {
+            if let Some(orderbook) = self.orderbook.as_mut() {
+                if orderbook.next().is_some() {
+                    self.line += 1;
+                    return Err(self.error("orderbook", "file is longer than the message file"));
+                }
+            }
+            return Ok(None);
+        };
+        self.line += 1;
+        let message = message.map_err(|err| PyIOError::new_err(err.to_string()))?;
+        let fields = self.parse_fields(&message, "message")?;
+        if fields.len() < 6 {
+            return Err(self.error("message", "expected 6 columns"));
+        }
+
+        let top = match self.orderbook.as_mut() {
+            Some(orderbook) => {
+                let Some(levels) = orderbook.next() else {
+                    return Err(self.error("orderbook", "file is shorter than the message file"));
+                };
+                let levels = levels.map_err(|err| PyIOError::new_err(err.to_string()))?;
+                let levels = self.parse_fields(&levels, "orderbook")?;
+                if levels.len() < 4 {
+                    return Err(self.error("orderbook", "expected at least 4 columns"));
+                }
+                Some(self.top_of_book(&levels))
+            }
+            None => None,
+        };
+
+        Ok(Some(LobsterRow {
+            time: fields[0],
+            event_type: fields[1] as i64,
+            order_id: fields[2] as i64,
+            size: fields[3],
+            price: fields[4] / self.price_scale,
+            direction: fields[5] as i64,
+            top,
+        }))
+    }
+
+    /// Level-1 columns are `ask_price, ask_size, bid_price, bid_size`; empty
+    /// levels carry placeholder prices and are reported as `(NaN, 0)`.
+    fn top_of_book(&self, levels: &[f64]) -> TopOfBook {
+        let level = |price: f64, size: f64| {
+            if price.abs() >= LOBSTER_EMPTY_PRICE {
+                (f64::NAN, 0.0)
+            } else {
+                (price / self.price_scale, size)
+            }
+        };
+        let (ask_price, ask_size) = level(levels[0], levels[1]);
+        let (bid_price, bid_size) = level(levels[2], levels[3]);
+        (bid_price, bid_size, ask_price, ask_size)
+    }
+
+    fn parse_fields(&self, text: &str, file: &str) -> PyResult<Vec<f64>> {
+        text.split(',')
+            .map(|field| {
+                field
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| self.error(file, &format!("invalid number {field:?}")))
+            })
+            .collect()
+    }
+
+    fn error(&self, file: &str, reason: &str) -> PyErr {
+        PyValueError::new_err(format!("LOBSTER {file} line {}: {reason}", self.line))
+    }
+}
+
+/// Load a LOBSTER message file (and optionally its orderbook file) into a dict
+/// of numpy arrays: `time`, `event_type`, `order_id`, `size`, `price`,
+/// `direction`, plus level-1 `bid_price`, `bid_size`, `ask_price`, `ask_size`
+/// when `orderbook_path` is given. Prices are divided by `price_scale`.
+#[pyfunction]
+#[pyo3(signature = (message_path, orderbook_path = None, price_scale = LOBSTER_PRICE_SCALE))]
+pub fn parse_lobster_csv<'py>(
+    py: Python<'py>,
+    message_path: &str,
+    orderbook_path: Option<&str>,
+    price_scale: f64,
+) -> PyResult<&'py PyDict> {
+    let mut reader = LobsterReader::open(message_path, orderbook_path, price_scale)?;
+    let mut time = Vec::new();
+    let mut event_type = Vec::new();
+    let mut order_id = Vec::new();
+    let mut size = Vec::new();
+    let mut price = Vec::new();
+    let mut direction = Vec::new();
+    let mut tops = Vec::new();
+    while let Some(row) = reader.next_row()? {
+        time.push(row.time);
+        event_type.push(row.event_type);
+        order_id.push(row.order_id);
+        size.push(row.size);
+        price.push(row.price);
+        direction.push(row.direction);
+        if let Some(top) = row.top {
+            tops.push(top);
+        }
+    }
+
+    let dict = PyDict::new(py);
+    dict.set_item("time", time.into_pyarray(py))?;
+    dict.set_item("event_type", event_type.into_pyarray(py))?;
+    dict.set_item("order_id", order_id.into_pyarray(py))?;
+    dict.set_item("size", size.into_pyarray(py))?;
+    dict.set_item("price", price.into_pyarray(py))?;
+    dict.set_item("direction", direction.into_pyarray(py))?;
+    if orderbook_path.is_some() {
+        let column = |f: fn(&TopOfBook) -> f64| tops.iter().map(f).collect::<Vec<f64>>();
+        dict.set_item("bid_price", column(|t| t.0).into_pyarray(py))?;
+        dict.set_item("bid_size", column(|t| t.1).into_pyarray(py))?;
+        dict.set_item("ask_price", column(|t| t.2).into_pyarray(py))?;
+        dict.set_item("ask_size", column(|t| t.3).into_pyarray(py))?;
+    }
+    Ok(dict)
+}
+
+/// Stream a LOBSTER file pair through `pipeline` without materializing it and
+/// return `pipeline.latest()`.
+///
+/// Executions (types 4 and 5) become signed trades, top-of-book changes become
+/// quotes, and trading halts (type 7) become system events: halt, quoting
+/// resumed (`EVENT_PRE_OPEN`) and trading resumed (`EVENT_RESUME`).
+#[pyfunction]
+#[pyo3(signature = (message_path, pipeline, orderbook_path = None, price_scale = LOBSTER_PRICE_SCALE))]
+pub fn run_through_pipeline<'py>(
+    py: Python<'py>,
+    message_path: &str,
+    mut pipeline: PyRefMut<'py, RustPipeline>,
+    orderbook_path: Option<&str>,
+    price_scale: f64,
+) -> PyResult<&'py PyDict> {
+    let mut reader = LobsterReader::open(message_path, orderbook_path, price_scale)?;
+    let mut last_top = None;
+    while let Some(row) = reader.next_row()? {
+        for message in row.messages(&mut last_top) {
+            pipeline.consume(&message)?;
+        }
+    }
+    pipeline.latest(py)
+}
+
+/// Normalized messages for one decoded ITCH event: executions and non-cross
+/// trades become trades signed against the resting side, the market-hours
+/// system events become session events, and a changed top of book is quoted.
+fn itch_messages(
+    event: &ItchEvent,
+    top: Option<TopOfBook>,
+    last_top: &mut Option<TopOfBook>,
+) -> Vec<Message> {
+    let mut out = Vec::with_capacity(2);
+    match event.msg_type {
+        b'E' | b'C' | b'P' if event.price.is_finite() && event.shares > 0.0 => {
+            out.push(Message::Trade {
+                timestamp: event.timestamp,
+                price: event.price,
+                volume: event.shares,
+                // Unknown resting side (0) leaves the sign to Lee-Ready.
+                sign: -(event.side as f64),
+            })
+        }
+        b'S' => {
+            // Start of system hours opens order entry before the market opens;
+            // start-of-messages and end-of-system markers are not mapped.
+            let event_code = match event.event_code {
+                b'S' => Some(EVENT_PRE_OPEN),
+                b'Q' => Some(EVENT_OPEN),
+                b'M' => Some(EVENT_CLOSE),
+                _ => None,
+            };
+            if let Some(event_code) = event_code {
+                out.push(Message::System {
+                    timestamp: event.timestamp,
+                    event_code,
+                });
+            }
+        }
+        _ => {}
+    }
+    if top != *last_top {
+        *last_top = top;
+        if let Some((bid_price, bid_size, ask_price, ask_size)) = top {
+            out.push(Message::Quote {
+                timestamp: event.timestamp,
+                bid_price,
+                bid_size,
+                ask_price,
+                ask_size,
+            });
+        }
+    }
+    out
+}
+
+/// Streams length-prefixed ITCH 5.0 messages from a file into the decoder.
+struct ItchReader {
+    reader: BufReader<File>,
+    decoder: ItchDecoder,
+    offset: u64,
+    body: Vec<u8>,
+}
+
+impl ItchReader {
+    fn open(path: &str, stock: Option<&str>, price_scale: f64) -> PyResult<Self> {
+        let decoder = ItchDecoder::new(stock, price_scale)?;
+        let file = File::open(path)
+            .map_err(|err| PyIOError::new_err(format!("cannot open {path}: {err}")))?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            decoder,
+            offset: 0,
+            body: Vec::new(),
+        })
+    }
+
+    fn next_row(&mut self) -> PyResult<Option<ItchEvent>> {
+        loop {
+            // End of file is clean only on a message boundary.
+            let mut len = [0u8; 2];
+            match self.reader.read_exact(&mut len[..1]) {
+                Ok(()) => {}
+                Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+                Err(err) => return Err(PyIOError::new_err(err.to_string())),
+            }
+            self.read_exact(&mut len[1..])?;
+            let len = u16::from_be_bytes(len) as usize;
+            let mut body = std::mem::take(&mut self.body);
+            body.resize(len, 0);
+            self.read_exact(&mut body)?;
+            let row = self
+                .decoder
+                .decode(&body)
+                .map_err(|reason| self.error(&reason));
+            self.offset += 2 + len as u64;
+            self.body = body;
+            if let Some(row) = row? {
+                return Ok(Some(row));
+            }
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> PyResult<()> {
+        self.reader.read_exact(buf).map_err(|err| match err.kind() {
+            ErrorKind::UnexpectedEof => self.error("truncated message"),
+            _ => PyIOError::new_err(err.to_string()),
+        })
+    }
+
+    fn error(&self, reason: &str) -> PyErr {
+        PyValueError::new_err(format!("ITCH message at byte {}: {reason}", self.offset))
+    }
+}
+
+/// Load a binary TotalView-ITCH 5.0 file (2-byte big-endian length prefix per
+/// message) into a dict of numpy arrays.
+///
+/// Columns: `timestamp` (seconds after midnight), `msg_type` (ASCII code),
+/// `stock_locate`, `order_ref`, `side` (+1 buy / -1 sell, the resting order's
+/// side for executions, cancels and deletes), `shares`, `price` (NaN when
+/// unknown) and `event_code` (system events only). Add, execute, cancel,
+/// delete, replace, non-cross trade and system event messages are kept; with
+/// `stock` only that symbol's messages (plus system events) are returned.
+#[pyfunction]
+#[pyo3(signature = (path, stock = None, price_scale = ITCH_PRICE_SCALE))]
+pub fn parse_itch_file<'py>(
+    py: Python<'py>,
+    path: &str,
+    stock: Option<&str>,
+    price_scale: f64,
+) -> PyResult<&'py PyDict> {
+    let mut reader = ItchReader::open(path, stock, price_scale)?;
+    let mut timestamp = Vec::new();
+    let mut msg_type = Vec::new();
+    let mut stock_locate = Vec::new();
+    let mut order_ref = Vec::new();
+    let mut side = Vec::new();
+    let mut shares = Vec::new();
+    let mut price = Vec::new();
+    let mut event_code = Vec::new();
+    while let Some(row) = reader.next_row()? {
+        timestamp.push(row.timestamp);
+        msg_type.push(row.msg_type);
+        stock_locate.push(row.stock_locate);
+        order_ref.push(row.order_ref);
+        side.push(row.side);
+        shares.push(row.shares);
+        price.push(row.price);
+        event_code.push(row.event_code);
+    }
+
+    let dict = PyDict::new(py);
+    dict.set_item("timestamp", timestamp.into_pyarray(py))?;
+    dict.set_item("msg_type", msg_type.into_pyarray(py))?;
+    dict.set_item("stock_locate", stock_locate.into_pyarray(py))?;
+    dict.set_item("order_ref", order_ref.into_pyarray(py))?;
+    dict.set_item("side", side.into_pyarray(py))?;
+    dict.set_item("shares", shares.into_pyarray(py))?;
+    dict.set_item("price", price.into_pyarray(py))?;
+    dict.set_item("event_code", event_code.into_pyarray(py))?;
+    Ok(dict)
+}
+
+/// Stream one symbol of a TotalView-ITCH 5.0 file through `pipeline` and
+/// return `pipeline.latest()`.
+///
+/// Resting orders are aggregated into a book whose top is quoted whenever it
+/// changes; executions and non-cross trades are signed against the resting
+/// side. System events map start of system hours to `EVENT_PRE_OPEN`, start
+/// of market hours to `EVENT_OPEN` and end of market hours to `EVENT_CLOSE`.
+#[pyfunction]
+#[pyo3(signature = (path, pipeline, stock, price_scale = ITCH_PRICE_SCALE))]
+pub fn run_itch_through_pipeline<'py>(
+    py: Python<'py>,
+    path: &str,
+    mut pipeline: PyRefMut<'py, RustPipeline>,
+    stock: &str,
+    price_scale: f64,
+) -> PyResult<&'py PyDict> {
+    let mut reader = ItchReader::open(path, Some(stock), price_scale)?;
+    let mut last_top = None;
+    while let Some(event) = reader.next_row()? {
+        let top = reader.decoder.top_of_book();
+        for message in itch_messages(&event, top, &mut last_top) {
+            pipeline.consume(&message)?;
+        }
+    }
+    pipeline.latest(py)
+}
@@ -0,0 +1,123 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+const MIN_TIME_EPS: f64 = 1e-12;
+
+/// Exponential ACD(1,1) (Engle-Russell) expected durations:
+/// `psi_i = omega + alpha * x_{i-1} + beta * psi_{i-1}`.
+#[pyclass]
+pub struct RustAcdModel {
+    omega: f64,
+    alpha: f64,
+    beta: f64,
+    expected_duration: f64,
+    last_timestamp: Option<f64>,
+}
+
+#[pymethods]
+impl RustAcdModel {
+    #[new]
+    #[pyo3(text_signature = "(omega, alpha, beta)")]
+    pub fn new(omega: f64, alpha: f64, beta: f64) -> PyResult<Self> {
+        if !omega.is_finite() || omega <= 0.0 {
+            return Err(PyValueError::new_err("omega must be finite and > 0"));
+        }
+        if !alpha.is_finite() || alpha < 0.0 {
+            return Err(PyValueError::new_err("alpha must be finite and >= 0"));
+        }
+        if !beta.is_finite() || beta < 0.0 {
+            return Err(PyValueError::new_err("beta must be finite and >= 0"));
+        }
+        if alpha + beta >= 1.0 {
+            return Err(PyValueError::new_err(
+                "alpha + beta must be < 1 for a stationary ACD model",
+            ));
+        }
+        let mut model = Self {
+            omega,
+            alpha,
+            beta,
+            expected_duration: 0.0,
+            last_timestamp: None,
+        };
+        model.reset();
+        Ok(model)
+    }
+
+    /// Restart from the unconditional mean duration `omega / (1 - alpha - beta)`.
+    pub fn reset(&mut self) {
+        self.expected_duration = self.unconditional_duration();
+        self.last_timestamp = None;
+    }
+
+    /// Register an event; returns the standardized duration `x_i / psi_i`
+    /// (mean 1 under the model), or `None` for the first event.
+    pub fn update(&mut self, timestamp: f64) -> PyResult<Option<f64>> {
+        if !timestamp.is_finite() {
+            return Err(PyValueError::new_err(
+                "timestamps supplied to ACD model must be finite",
+            ));
+        }
+        let Some(last_ts) = self.last_timestamp else {
+            self.last_timestamp = Some(timestamp);
+            return Ok(None);
+        };
+        if timestamp + MIN_TIME_EPS < last_ts {
+            return Err(PyValueError::new_err(
+                "timestamps must be non-decreasing for ACD updates",
+            ));
+        }
+        let duration = (timestamp - last_ts).max(0.0);
+        let surprise = duration / self.expected_duration;
+        self.expected_duration =
+            self.omega + self.alpha * duration + self.beta * self.expected_duration;
+        self.last_timestamp = Some(timestamp);
+        Ok(Some(surprise))
+    }
+
+    pub fn update_many<'py>(
+        &mut self,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        self.series(timestamps.as_slice()?, |x| x)
+    }
+
+    /// `update_many` returning a float64 array with NaN for the first event.
+    pub fn update_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out = self.series(timestamps.as_slice()?, |x| x.unwrap_or(f64::NAN))?;
+        Ok(out.into_pyarray(py))
+    }
+
+    /// Conditional expected duration until the next event.
+    pub fn expected_duration(&self) -> f64 {
+        self.expected_duration
+    }
+
+    /// Conditional event rate `1 / psi`, comparable to a Hawkes intensity.
+    pub fn expected_intensity(&self) -> f64 {
+        1.0 / self.expected_duration
+    }
+
+    pub fn unconditional_duration(&self) -> f64 {
+        self.omega / (1.0 - self.alpha - self.beta)
+    }
+}
+
+impl RustAcdModel {
+    fn series<T>(
+        &mut self,
+        timestamps: &[f64],
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        let mut out = Vec::with_capacity(timestamps.len());
+        for &ts in timestamps {
+            out.push(emit(self.update(ts)?));
+        }
+        Ok(out)
+    }
+}
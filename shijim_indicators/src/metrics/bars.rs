@@ -0,0 +1,456 @@
+use numpy::{IntoPyArray, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+const TIME_EPS: f64 = 1e-12;
+const PRICE_EPS: f64 = 1e-12;
+
+/// Field order of the bars emitted by `RustBarSampler`.
+const BAR_FIELDS: [&str; 9] = [
+    "start_ts",
+    "end_ts",
+    "open",
+    "high",
+    "low",
+    "close",
+    "volume",
+    "vwap",
+    "trade_count",
+];
+
+type BarTuple = (f64, f64, f64, f64, f64, f64, f64, f64, u64);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BarMode {
+    Time,
+    Volume,
+    /// Closes once traded notional (price * volume) reaches the threshold.
+    Dollar,
+    /// Closes once |sum(sign * volume)| reaches the threshold.
+    Imbalance,
+}
+
+impl BarMode {
+    fn parse(mode: &str) -> PyResult<Self> {
+        match mode {
+            "time" => Ok(Self::Time),
+            "volume" => Ok(Self::Volume),
+            "dollar" => Ok(Self::Dollar),
+            "imbalance" => Ok(Self::Imbalance),
+            _ => Err(PyValueError::new_err(
+                "bar mode must be one of 'time', 'volume', 'dollar' or 'imbalance'",
+            )),
+        }
+    }
+}
+
+/// OHLCV accumulator shared by the bar builders.
+#[derive(Clone, Debug)]
+struct OhlcBar {
+    start_ts: f64,
+    end_ts: f64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    notional: f64,
+    trade_count: u64,
+}
+
+impl OhlcBar {
+    fn new(start_ts: f64, price: f64, volume: f64, timestamp: f64) -> Self {
+        Self {
+            start_ts,
+            end_ts: timestamp,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+            notional: price * volume,
+            trade_count: 1,
+        }
+    }
+
+    fn push(&mut self, price: f64, volume: f64, timestamp: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += volume;
+        self.notional += price * volume;
+        self.trade_count += 1;
+        self.end_ts = timestamp;
+    }
+
+    fn vwap(&self) -> f64 {
+        if self.volume > 0.0 {
+            self.notional / self.volume
+        } else {
+            self.close
+        }
+    }
+
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        dict.set_item("start_ts", self.start_ts)?;
+        dict.set_item("end_ts", self.end_ts)?;
+        dict.set_item("open", self.open)?;
+        dict.set_item("high", self.high)?;
+        dict.set_item("low", self.low)?;
+        dict.set_item("close", self.close)?;
+        dict.set_item("volume", self.volume)?;
+        dict.set_item("vwap", self.vwap())?;
+        dict.set_item("trade_count", self.trade_count)?;
+        Ok(dict.into_py(py))
+    }
+
+    fn to_tuple(&self) -> BarTuple {
+        (
+            self.start_ts,
+            self.end_ts,
+            self.open,
+            self.high,
+            self.low,
+            self.close,
+            self.volume,
+            self.vwap(),
+            self.trade_count,
+        )
+    }
+}
+
+/// Mode-specific bar accumulation shared by `RustBarBuilder` and `RustBarSampler`.
+struct BarAccumulator {
+    mode: BarMode,
+    threshold: f64,
+    current: Option<OhlcBar>,
+    last_timestamp: Option<f64>,
+    last_price: Option<f64>,
+    last_tick_sign: f64,
+    imbalance: f64,
+}
+
+impl BarAccumulator {
+    fn new(mode: &str, threshold: f64) -> PyResult<Self> {
+        let mode = BarMode::parse(mode)?;
+        if !threshold.is_finite() || threshold <= 0.0 {
+            return Err(PyValueError::new_err(
+                "threshold must be a positive, finite number",
+            ));
+        }
+        Ok(Self {
+            mode,
+            threshold,
+            current: None,
+            last_timestamp: None,
+            last_price: None,
+            last_tick_sign: 0.0,
+            imbalance: 0.0,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.current = None;
+        self.last_timestamp = None;
+        self.last_price = None;
+        self.last_tick_sign = 0.0;
+        self.imbalance = 0.0;
+    }
+
+    fn flush(&mut self) -> Option<OhlcBar> {
+        self.imbalance = 0.0;
+        self.current.take()
+    }
+
+    fn pending_trade_count(&self) -> u64 {
+        self.current.as_ref().map_or(0, |bar| bar.trade_count)
+    }
+
+    /// Feed one trade. `sign` is +1 / -1 for known aggressor sides, or 0 to fall
+    /// back to the tick rule; it only matters for imbalance bars.
+    fn consume_tick(
+        &mut self,
+        price: f64,
+        volume: f64,
+        timestamp: f64,
+        sign: f64,
+    ) -> PyResult<Option<OhlcBar>> {
+        Self::validate_tick(price, volume, timestamp)?;
+        if sign != 1.0 && sign != -1.0 && sign != 0.0 {
+            return Err(PyValueError::new_err("sign must be 1, -1 or 0"));
+        }
+        if let Some(last_ts) = self.last_timestamp {
+            if timestamp + TIME_EPS < last_ts {
+                return Err(PyValueError::new_err(
+                    "timestamps must be non-decreasing for bar aggregation",
+                ));
+            }
+        }
+        self.last_timestamp = Some(timestamp);
+        let tick_sign = self.tick_sign(price);
+
+        match self.mode {
+            BarMode::Time => Ok(self.consume_time_tick(price, volume, timestamp)),
+            BarMode::Volume | BarMode::Dollar | BarMode::Imbalance => {
+                if self.mode == BarMode::Imbalance {
+                    let sign = if sign != 0.0 { sign } else { tick_sign };
+                    self.imbalance += sign * volume;
+                }
+                Ok(self.consume_threshold_tick(price, volume, timestamp))
+            }
+        }
+    }
+
+    fn consume_time_tick(&mut self, price: f64, volume: f64, timestamp: f64) -> Option<OhlcBar> {
+        let bucket_start = (timestamp / self.threshold).floor() * self.threshold;
+        match self.current.as_mut() {
+            Some(bar) if timestamp < bar.start_ts + self.threshold => {
+                bar.push(price, volume, timestamp);
+                None
+            }
+            _ => self
+                .current
+                .replace(OhlcBar::new(bucket_start, price, volume, timestamp)),
+        }
+    }
+
+    fn consume_threshold_tick(
+        &mut self,
+        price: f64,
+        volume: f64,
+        timestamp: f64,
+    ) -> Option<OhlcBar> {
+        match self.current.as_mut() {
+            Some(bar) => bar.push(price, volume, timestamp),
+            None => self.current = Some(OhlcBar::new(timestamp, price, volume, timestamp)),
+        }
+        let full = self.current.as_ref().is_some_and(|bar| match self.mode {
+            BarMode::Volume => bar.volume >= self.threshold,
+            BarMode::Dollar => bar.notional >= self.threshold,
+            BarMode::Imbalance => self.imbalance.abs() >= self.threshold,
+            BarMode::Time => false,
+        });
+        if full {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    /// Tick rule, carrying the previous sign through zero ticks.
+    fn tick_sign(&mut self, price: f64) -> f64 {
+        if let Some(last) = self.last_price {
+            if price > last + PRICE_EPS {
+                self.last_tick_sign = 1.0;
+            } else if price + PRICE_EPS < last {
+                self.last_tick_sign = -1.0;
+            }
+        }
+        self.last_price = Some(price);
+        self.last_tick_sign
+    }
+
+    fn validate_tick(price: f64, volume: f64, timestamp: f64) -> PyResult<()> {
+        if !price.is_finite() {
+            return Err(PyValueError::new_err("price must be a finite float"));
+        }
+        if !volume.is_finite() || volume < 0.0 {
+            return Err(PyValueError::new_err("volume must be finite and >= 0"));
+        }
+        if !timestamp.is_finite() {
+            return Err(PyValueError::new_err("timestamp must be a finite float"));
+        }
+        Ok(())
+    }
+}
+
+#[pyclass]
+pub struct RustBarBuilder {
+    inner: BarAccumulator,
+}
+
+#[pymethods]
+impl RustBarBuilder {
+    #[new]
+    #[pyo3(text_signature = "(mode, threshold)")]
+    pub fn new(mode: &str, threshold: f64) -> PyResult<Self> {
+        Ok(Self {
+            inner: BarAccumulator::new(mode, threshold)?,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Feed one trade; returns the bar completed by this update, if any.
+    pub fn update(
+        &mut self,
+        py: Python<'_>,
+        price: f64,
+        volume: f64,
+        timestamp: f64,
+    ) -> PyResult<Option<PyObject>> {
+        match self.inner.consume_tick(price, volume, timestamp, 0.0)? {
+            Some(bar) => Ok(Some(bar.to_dict(py)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Emit the partially built bar (if any) and start from scratch.
+    pub fn flush(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        match self.inner.flush() {
+            Some(bar) => Ok(Some(bar.to_dict(py)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn pending_trade_count(&self) -> u64 {
+        self.inner.pending_trade_count()
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.inner.threshold
+    }
+}
+
+/// Samples ticks into time, volume, dollar or imbalance bars.
+///
+/// Bars are `(start_ts, end_ts, open, high, low, close, volume, vwap,
+/// trade_count)` tuples; `update_many` returns the completed bars of a batch as
+/// a numpy record array with the same field names.
+#[pyclass]
+pub struct RustBarSampler {
+    inner: BarAccumulator,
+}
+
+#[pymethods]
+impl RustBarSampler {
+    #[new]
+    #[pyo3(text_signature = "(mode, threshold)")]
+    pub fn new(mode: &str, threshold: f64) -> PyResult<Self> {
+        Ok(Self {
+            inner: BarAccumulator::new(mode, threshold)?,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Feed one trade; returns the bar completed by this update, if any.
+    #[pyo3(signature = (price, volume, timestamp, sign = 0.0))]
+    pub fn update(
+        &mut self,
+        price: f64,
+        volume: f64,
+        timestamp: f64,
+        sign: f64,
+    ) -> PyResult<Option<BarTuple>> {
+        Ok(self
+            .inner
+            .consume_tick(price, volume, timestamp, sign)?
+            .map(|bar| bar.to_tuple()))
+    }
+
+    /// Feed a batch of trades; returns the bars completed within it as a numpy
+    /// record array (see `fields()`).
+    #[pyo3(signature = (prices, volumes, timestamps, signs = None))]
+    pub fn update_many<'py>(
+        &mut self,
+        py: Python<'py>,
+        prices: PyReadonlyArray1<'py, f64>,
+        volumes: PyReadonlyArray1<'py, f64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+        signs: Option<PyReadonlyArray1<'py, f64>>,
+    ) -> PyResult<PyObject> {
+        let prices = prices.as_slice()?;
+        let volumes = volumes.as_slice()?;
+        let timestamps = timestamps.as_slice()?;
+        let signs = match signs.as_ref() {
+            Some(signs) => Some(signs.as_slice()?),
+            None => None,
+        };
+        if prices.len() != volumes.len()
+            || prices.len() != timestamps.len()
+            || signs.is_some_and(|s| s.len() != prices.len())
+        {
+            return Err(PyValueError::new_err(
+                "prices/volumes/timestamps/signs arrays must have matching length",
+            ));
+        }
+        let mut bars = Vec::new();
+        for (i, ((&price, &volume), &ts)) in prices.iter().zip(volumes).zip(timestamps).enumerate()
+        {
+            let sign = signs.map_or(0.0, |s| s[i]);
+            if let Some(bar) = self.inner.consume_tick(price, volume, ts, sign)? {
+                bars.push(bar);
+            }
+        }
+        Self::to_record_array(py, &bars)
+    }
+
+    /// Emit the partially built bar (if any) and start from scratch.
+    pub fn flush(&mut self) -> Option<BarTuple> {
+        self.inner.flush().map(|bar| bar.to_tuple())
+    }
+
+    /// Names of the bar tuple / record array fields, in order.
+    #[staticmethod]
+    pub fn fields() -> Vec<&'static str> {
+        BAR_FIELDS.to_vec()
+    }
+
+    /// Signed volume accumulated in the pending imbalance bar.
+    pub fn imbalance(&self) -> f64 {
+        self.inner.imbalance
+    }
+
+    pub fn pending_trade_count(&self) -> u64 {
+        self.inner.pending_trade_count()
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.inner.threshold
+    }
+}
+
+impl RustBarSampler {
+    fn to_record_array(py: Python<'_>, bars: &[OhlcBar]) -> PyResult<PyObject> {
+        let column = |f: fn(&OhlcBar) -> f64| -> PyObject {
+            bars.iter()
+                .map(f)
+                .collect::<Vec<f64>>()
+                .into_pyarray(py)
+                .into_py(py)
+        };
+        let columns = PyList::new(
+            py,
+            [
+                column(|b| b.start_ts),
+                column(|b| b.end_ts),
+                column(|b| b.open),
+                column(|b| b.high),
+                column(|b| b.low),
+                column(|b| b.close),
+                column(|b| b.volume),
+                column(OhlcBar::vwap),
+                bars.iter()
+                    .map(|b| b.trade_count)
+                    .collect::<Vec<u64>>()
+                    .into_pyarray(py)
+                    .into_py(py),
+            ],
+        );
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("names", BAR_FIELDS.join(","))?;
+        let records = py.import("numpy")?.getattr("rec")?.call_method(
+            "fromarrays",
+            (columns,),
+            Some(kwargs),
+        )?;
+        Ok(records.into_py(py))
+    }
+}
@@ -1,8 +1,12 @@
-use numpy::PyReadonlyArray1;
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+use super::optimize::nelder_mead;
+
 const MIN_TIME_EPS: f64 = 1e-12;
+const FIT_MAX_ITER: usize = 2000;
+const FIT_TOL: f64 = 1e-10;
 
 #[pyclass]
 pub struct RustHawkesIntensity {
@@ -11,13 +15,19 @@ pub struct RustHawkesIntensity {
     beta: f64,
     last_intensity: f64,
     last_timestamp: Option<f64>,
+    require_strictly_increasing: bool,
 }
 
 #[pymethods]
 impl RustHawkesIntensity {
     #[new]
-    #[pyo3(text_signature = "(baseline, alpha, beta)")]
-    pub fn new(baseline: f64, alpha: f64, beta: f64) -> PyResult<Self> {
+    #[pyo3(signature = (baseline, alpha, beta, require_strictly_increasing = false))]
+    pub fn new(
+        baseline: f64,
+        alpha: f64,
+        beta: f64,
+        require_strictly_increasing: bool,
+    ) -> PyResult<Self> {
         if !baseline.is_finite() || baseline < 0.0 {
             return Err(PyValueError::new_err(
                 "baseline intensity must be finite and >= 0",
@@ -36,6 +46,7 @@ impl RustHawkesIntensity {
             beta,
             last_intensity: baseline,
             last_timestamp: None,
+            require_strictly_increasing,
         })
     }
 
@@ -52,6 +63,11 @@ impl RustHawkesIntensity {
                     "timestamps must be non-decreasing for Hawkes updates",
                 ));
             }
+            if self.require_strictly_increasing && timestamp <= last_ts + MIN_TIME_EPS {
+                return Err(PyValueError::new_err(
+                    "duplicate timestamp: Hawkes updates require strictly increasing times",
+                ));
+            }
             let dt = (timestamp - last_ts).max(0.0);
             let decayed = self.decayed_intensity(dt);
             self.last_intensity = decayed + self.alpha;
@@ -74,6 +90,19 @@ impl RustHawkesIntensity {
         Ok(out)
     }
 
+    /// `update_many` returning a float64 array instead of a list.
+    pub fn update_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        Ok(self.update_many(timestamps)?.into_pyarray(py))
+    }
+
+    pub fn requires_strictly_increasing(&self) -> bool {
+        self.require_strictly_increasing
+    }
+
     pub fn current_intensity(&self) -> f64 {
         self.last_intensity
     }
@@ -92,6 +121,58 @@ impl RustHawkesIntensity {
             Ok(self.baseline)
         }
     }
+
+    /// Log-likelihood of `timestamps` under the current parameters, observed
+    /// over `[timestamps[0], end_time]` (defaults to the last event).
+    #[pyo3(signature = (timestamps, end_time = None))]
+    pub fn log_likelihood<'py>(
+        &self,
+        timestamps: PyReadonlyArray1<'py, f64>,
+        end_time: Option<f64>,
+    ) -> PyResult<f64> {
+        let times = timestamps.as_slice()?;
+        let end = Self::validate_fit_input(times, end_time)?;
+        Ok(exp_kernel_log_likelihood(
+            self.baseline,
+            self.alpha,
+            self.beta,
+            times,
+            end,
+        ))
+    }
+
+    /// Maximum-likelihood estimate of `(baseline, alpha, beta)` for the
+    /// exponential kernel, using the Ozaki recursion and Nelder-Mead over
+    /// log-parameters. Returns `(baseline, alpha, beta, log_likelihood)`; with
+    /// `update_params` the calculator adopts the estimate and is reset.
+    #[pyo3(signature = (timestamps, end_time = None, update_params = true))]
+    pub fn fit<'py>(
+        &mut self,
+        timestamps: PyReadonlyArray1<'py, f64>,
+        end_time: Option<f64>,
+        update_params: bool,
+    ) -> PyResult<(f64, f64, f64, f64)> {
+        let times = timestamps.as_slice()?;
+        let end = Self::validate_fit_input(times, end_time)?;
+        let fitted = fit_exp_kernel(times, end);
+        let (baseline, alpha, beta, log_likelihood) = fitted;
+        if !log_likelihood.is_finite() {
+            return Err(PyValueError::new_err(
+                "Hawkes fit did not converge to a finite likelihood",
+            ));
+        }
+        if update_params {
+            self.baseline = baseline;
+            self.alpha = alpha;
+            self.beta = beta;
+            self.reset();
+        }
+        Ok(fitted)
+    }
+
+    pub fn params(&self) -> (f64, f64, f64) {
+        (self.baseline, self.alpha, self.beta)
+    }
 }
 
 impl RustHawkesIntensity {
@@ -103,6 +184,37 @@ impl RustHawkesIntensity {
         self.baseline + (self.last_intensity - self.baseline) * decay
     }
 
+    fn validate_fit_input(times: &[f64], end_time: Option<f64>) -> PyResult<f64> {
+        if times.len() < 2 {
+            return Err(PyValueError::new_err(
+                "at least two event timestamps are required",
+            ));
+        }
+        for pair in times.windows(2) {
+            Self::validate_timestamp(pair[0])?;
+            if pair[1] + MIN_TIME_EPS < pair[0] {
+                return Err(PyValueError::new_err(
+                    "timestamps must be non-decreasing for Hawkes fitting",
+                ));
+            }
+        }
+        let last = times[times.len() - 1];
+        Self::validate_timestamp(last)?;
+        let end = end_time.unwrap_or(last);
+        Self::validate_timestamp(end)?;
+        if end + MIN_TIME_EPS < last {
+            return Err(PyValueError::new_err(
+                "end_time must be >= the last event timestamp",
+            ));
+        }
+        if end - times[0] <= MIN_TIME_EPS {
+            return Err(PyValueError::new_err(
+                "observation window must have positive length",
+            ));
+        }
+        Ok(end)
+    }
+
     fn validate_timestamp(timestamp: f64) -> PyResult<()> {
         if !timestamp.is_finite() {
             return Err(PyValueError::new_err(
@@ -112,3 +224,48 @@ impl RustHawkesIntensity {
         Ok(())
     }
 }
+
+/// Exponential-kernel Hawkes log-likelihood over `[times[0], end]`.
+fn exp_kernel_log_likelihood(baseline: f64, alpha: f64, beta: f64, times: &[f64], end: f64) -> f64 {
+    let mut log_sum = 0.0;
+    let mut excitation = 0.0;
+    let mut compensator_tail = 0.0;
+    for (idx, &t) in times.iter().enumerate() {
+        if idx > 0 {
+            // Ozaki recursion: A_i = exp(-beta * dt) * (1 + A_{i-1}).
+            let dt = (t - times[idx - 1]).max(0.0);
+            excitation = (-beta * dt).exp() * (1.0 + excitation);
+        }
+        let intensity = baseline + alpha * excitation;
+        if intensity <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        log_sum += intensity.ln();
+        compensator_tail += 1.0 - (-beta * (end - t)).exp();
+    }
+    log_sum - baseline * (end - times[0]) - alpha / beta * compensator_tail
+}
+
+fn fit_exp_kernel(times: &[f64], end: f64) -> (f64, f64, f64, f64) {
+    let span = end - times[0];
+    let rate = times.len() as f64 / span;
+    // Moment-style start: branching ratio 0.5 with the observed mean rate.
+    let start = [(0.5 * rate).ln(), (0.5 * rate).ln(), rate.ln()];
+    let objective = |theta: &[f64]| {
+        let ll =
+            exp_kernel_log_likelihood(theta[0].exp(), theta[1].exp(), theta[2].exp(), times, end);
+        if ll.is_finite() {
+            -ll
+        } else {
+            f64::INFINITY
+        }
+    };
+    let (mut theta, mut value) = nelder_mead(objective, &start, 0.5, FIT_MAX_ITER, FIT_TOL);
+    // Restart once from the optimum to escape a collapsed simplex.
+    let (restart, restart_value) = nelder_mead(objective, &theta, 0.1, FIT_MAX_ITER, FIT_TOL);
+    if restart_value <= value {
+        theta = restart;
+        value = restart_value;
+    }
+    (theta[0].exp(), theta[1].exp(), theta[2].exp(), -value)
+}
@@ -0,0 +1,153 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+const VARIANCE_EPS: f64 = 1e-12;
+/// Flow variances below this fraction of the raw second moment are treated as zero.
+const RELATIVE_VARIANCE_EPS: f64 = 1e-12;
+
+/// Rolling OLS of price change on signed volume; the slope is Kyle's lambda.
+///
+/// Means and co-moments use Welford add/remove updates to avoid cancellation.
+#[pyclass]
+pub struct RustKyleLambda {
+    window_size: usize,
+    samples: VecDeque<(f64, f64)>,
+    mean_x: f64,
+    mean_y: f64,
+    m2_x: f64,
+    c_xy: f64,
+}
+
+#[pymethods]
+impl RustKyleLambda {
+    #[new]
+    #[pyo3(text_signature = "(window_size)")]
+    pub fn new(window_size: usize) -> PyResult<Self> {
+        if window_size < 2 {
+            return Err(PyValueError::new_err("window_size must be >= 2"));
+        }
+        Ok(Self {
+            window_size,
+            samples: VecDeque::with_capacity(window_size + 1),
+            mean_x: 0.0,
+            mean_y: 0.0,
+            m2_x: 0.0,
+            c_xy: 0.0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.mean_x = 0.0;
+        self.mean_y = 0.0;
+        self.m2_x = 0.0;
+        self.c_xy = 0.0;
+    }
+
+    pub fn update(&mut self, signed_volume: f64, price_change: f64) -> PyResult<Option<f64>> {
+        if !signed_volume.is_finite() || !price_change.is_finite() {
+            return Err(PyValueError::new_err(
+                "signed_volume and price_change must be finite floats",
+            ));
+        }
+        self.push_sample(signed_volume, price_change);
+        Ok(self.current_lambda())
+    }
+
+    pub fn update_many<'py>(
+        &mut self,
+        signed_volumes: PyReadonlyArray1<'py, f64>,
+        price_changes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        self.series(
+            signed_volumes.as_slice()?,
+            price_changes.as_slice()?,
+            |lambda| lambda,
+        )
+    }
+
+    /// `update_many` returning a float64 array with NaN until lambda is available.
+    pub fn update_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        signed_volumes: PyReadonlyArray1<'py, f64>,
+        price_changes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out = self.series(
+            signed_volumes.as_slice()?,
+            price_changes.as_slice()?,
+            |lambda| lambda.unwrap_or(f64::NAN),
+        )?;
+        Ok(out.into_pyarray(py))
+    }
+
+    /// Price impact per unit of signed volume, once the window is full.
+    pub fn current_lambda(&self) -> Option<f64> {
+        if self.samples.len() < self.window_size {
+            return None;
+        }
+        let n = self.samples.len() as f64;
+        let sxx = self.m2_x.max(0.0);
+        let raw_sxx = sxx + n * self.mean_x * self.mean_x;
+        if sxx <= VARIANCE_EPS.max(RELATIVE_VARIANCE_EPS * raw_sxx) {
+            return None;
+        }
+        Some(self.c_xy / sxx)
+    }
+
+    pub fn intercept(&self) -> Option<f64> {
+        let lambda = self.current_lambda()?;
+        Some(self.mean_y - lambda * self.mean_x)
+    }
+
+    pub fn samples_ready(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+}
+
+impl RustKyleLambda {
+    fn series<T>(
+        &mut self,
+        volumes: &[f64],
+        changes: &[f64],
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        if volumes.len() != changes.len() {
+            return Err(PyValueError::new_err(
+                "signed_volumes/price_changes arrays must have matching length",
+            ));
+        }
+        let mut out = Vec::with_capacity(volumes.len());
+        for (&volume, &change) in volumes.iter().zip(changes) {
+            out.push(emit(self.update(volume, change)?));
+        }
+        Ok(out)
+    }
+
+    fn push_sample(&mut self, x: f64, y: f64) {
+        // Welford add, then remove the expired sample.
+        self.samples.push_back((x, y));
+        let n = self.samples.len() as f64;
+        let dx = x - self.mean_x;
+        self.mean_x += dx / n;
+        self.mean_y += (y - self.mean_y) / n;
+        self.m2_x += dx * (x - self.mean_x);
+        self.c_xy += dx * (y - self.mean_y);
+        if self.samples.len() > self.window_size {
+            if let Some((old_x, old_y)) = self.samples.pop_front() {
+                let n = self.samples.len() as f64;
+                let dx = old_x - self.mean_x;
+                self.mean_x -= dx / n;
+                self.mean_y -= (old_y - self.mean_y) / n;
+                self.m2_x -= dx * (old_x - self.mean_x);
+                self.c_xy -= dx * (old_y - self.mean_y);
+            }
+        }
+    }
+}
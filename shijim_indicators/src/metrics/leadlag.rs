@@ -0,0 +1,261 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+const VARIANCE_EPS: f64 = 1e-12;
+const MIN_TIME_EPS: f64 = 1e-12;
+
+/// Return intervals `(start, end, return)` of one instrument that may still
+/// overlap intervals of the other; ends are non-decreasing.
+type Intervals = VecDeque<(f64, f64, f64)>;
+
+struct LagState {
+    lag: f64,
+    intervals: [Intervals; 2],
+    covariance: f64,
+}
+
+impl LagState {
+    fn shift(&self, instrument: usize) -> f64 {
+        if instrument == 1 {
+            self.lag
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Hayashi-Yoshida covariance/correlation of two asynchronously observed
+/// return streams, optionally at several lags.
+///
+/// Each pair of return intervals whose time spans overlap contributes the
+/// product of their returns; a pair is counted when the later of the two
+/// arrives, so updates are incremental. For a lag `theta` instrument 1's
+/// intervals are shifted forward by `theta`, so a positive lag with the
+/// largest |covariance| means instrument 1 leads instrument 0.
+#[pyclass]
+pub struct RustHayashiYoshida {
+    states: Vec<LagState>,
+    last_timestamps: [Option<f64>; 2],
+    realized_variances: [f64; 2],
+}
+
+#[pymethods]
+impl RustHayashiYoshida {
+    #[new]
+    #[pyo3(signature = (lags = None))]
+    pub fn new(lags: Option<Vec<f64>>) -> PyResult<Self> {
+        let lags = lags.unwrap_or_else(|| vec![0.0]);
+        if lags.is_empty() {
+            return Err(PyValueError::new_err("at least one lag is required"));
+        }
+        if lags.iter().any(|lag| !lag.is_finite()) {
+            return Err(PyValueError::new_err("lags must be finite"));
+        }
+        Ok(Self {
+            states: lags
+                .into_iter()
+                .map(|lag| LagState {
+                    lag,
+                    intervals: [VecDeque::new(), VecDeque::new()],
+                    covariance: 0.0,
+                })
+                .collect(),
+            last_timestamps: [None, None],
+            realized_variances: [0.0, 0.0],
+        })
+    }
+
+    pub fn reset(&mut self) {
+        for state in &mut self.states {
+            state.intervals.iter_mut().for_each(VecDeque::clear);
+            state.covariance = 0.0;
+        }
+        self.last_timestamps = [None, None];
+        self.realized_variances = [0.0, 0.0];
+    }
+
+    /// Record the return of `instrument_id` (0 or 1) over the interval since its
+    /// previous observation; returns the correlation at the first lag.
+    ///
+    /// The first observation of each instrument only opens its first interval,
+    /// so its return is ignored. Timestamps must be strictly increasing per
+    /// instrument; aggregate same-timestamp returns before calling. Feed both
+    /// instruments in merged timestamp order: while one side is silent the
+    /// other only keeps the intervals its first observation could still overlap.
+    pub fn update(
+        &mut self,
+        instrument_id: usize,
+        timestamp: f64,
+        ret: f64,
+    ) -> PyResult<Option<f64>> {
+        if instrument_id > 1 {
+            return Err(PyValueError::new_err("instrument_id must be 0 or 1"));
+        }
+        if !timestamp.is_finite() || !ret.is_finite() {
+            return Err(PyValueError::new_err(
+                "timestamp and return must be finite floats",
+            ));
+        }
+        let Some(start) = self.last_timestamps[instrument_id] else {
+            self.last_timestamps[instrument_id] = Some(timestamp);
+            return Ok(self.correlation());
+        };
+        if timestamp <= start + MIN_TIME_EPS {
+            return Err(PyValueError::new_err(
+                "timestamps must be strictly increasing per instrument",
+            ));
+        }
+
+        let other = 1 - instrument_id;
+        let other_last = self.last_timestamps[other];
+        for state in &mut self.states {
+            let shift = state.shift(instrument_id);
+            let (begin, end) = (start + shift, timestamp + shift);
+
+            let overlap: f64 = state.intervals[other]
+                .iter()
+                .take_while(|&&(other_begin, _, _)| other_begin < end)
+                .filter(|&&(_, other_end, _)| other_end > begin)
+                .map(|&(_, _, other_ret)| other_ret)
+                .sum();
+            state.covariance += ret * overlap;
+            state.intervals[instrument_id].push_back((begin, end, ret));
+
+            // Future intervals of this instrument start at `end`, and those of
+            // the other instrument at its last timestamp (or, before its first
+            // observation, no earlier than this one).
+            while state.intervals[other]
+                .front()
+                .is_some_and(|&(_, other_end, _)| other_end <= end)
+            {
+                state.intervals[other].pop_front();
+            }
+            let other_begin = other_last.unwrap_or(timestamp) + state.shift(other);
+            while state.intervals[instrument_id]
+                .front()
+                .is_some_and(|&(_, own_end, _)| own_end <= other_begin)
+            {
+                state.intervals[instrument_id].pop_front();
+            }
+        }
+        self.realized_variances[instrument_id] += ret * ret;
+        self.last_timestamps[instrument_id] = Some(timestamp);
+        Ok(self.correlation())
+    }
+
+    pub fn update_many<'py>(
+        &mut self,
+        instrument_ids: PyReadonlyArray1<'py, i64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+        returns: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        self.series(
+            instrument_ids.as_slice()?,
+            timestamps.as_slice()?,
+            returns.as_slice()?,
+            |corr| corr,
+        )
+    }
+
+    /// `update_many` returning a float64 array with NaN where no correlation exists.
+    pub fn update_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        instrument_ids: PyReadonlyArray1<'py, i64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+        returns: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out = self.series(
+            instrument_ids.as_slice()?,
+            timestamps.as_slice()?,
+            returns.as_slice()?,
+            |corr| corr.unwrap_or(f64::NAN),
+        )?;
+        Ok(out.into_pyarray(py))
+    }
+
+    /// Hayashi-Yoshida covariance at the first lag.
+    pub fn covariance(&self) -> f64 {
+        self.states[0].covariance
+    }
+
+    /// Covariance normalized by both realized variances, at the first lag.
+    pub fn correlation(&self) -> Option<f64> {
+        self.correlation_of(&self.states[0])
+    }
+
+    pub fn covariances(&self) -> Vec<f64> {
+        self.states.iter().map(|state| state.covariance).collect()
+    }
+
+    pub fn correlations(&self) -> Vec<Option<f64>> {
+        self.states
+            .iter()
+            .map(|state| self.correlation_of(state))
+            .collect()
+    }
+
+    /// Lag with the largest absolute covariance, once both instruments have
+    /// non-zero realized variance.
+    pub fn lead_lag(&self) -> Option<f64> {
+        self.correlation_denominator()?;
+        self.states
+            .iter()
+            .max_by(|a, b| a.covariance.abs().total_cmp(&b.covariance.abs()))
+            .map(|state| state.lag)
+    }
+
+    pub fn lags(&self) -> Vec<f64> {
+        self.states.iter().map(|state| state.lag).collect()
+    }
+
+    pub fn realized_variances(&self) -> (f64, f64) {
+        (self.realized_variances[0], self.realized_variances[1])
+    }
+
+    /// Number of return intervals kept across all lags for future overlaps.
+    pub fn buffered_intervals(&self) -> usize {
+        self.states
+            .iter()
+            .flat_map(|state| &state.intervals)
+            .map(VecDeque::len)
+            .sum()
+    }
+}
+
+impl RustHayashiYoshida {
+    fn series<T>(
+        &mut self,
+        instrument_ids: &[i64],
+        timestamps: &[f64],
+        returns: &[f64],
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        if instrument_ids.len() != timestamps.len() || instrument_ids.len() != returns.len() {
+            return Err(PyValueError::new_err(
+                "instrument_ids/timestamps/returns arrays must have matching length",
+            ));
+        }
+        let mut out = Vec::with_capacity(returns.len());
+        for ((&id, &ts), &ret) in instrument_ids.iter().zip(timestamps).zip(returns) {
+            let id = usize::try_from(id)
+                .map_err(|_| PyValueError::new_err("instrument_id must be 0 or 1"))?;
+            out.push(emit(self.update(id, ts, ret)?));
+        }
+        Ok(out)
+    }
+
+    fn correlation_denominator(&self) -> Option<f64> {
+        let [rv0, rv1] = self.realized_variances;
+        if rv0 <= VARIANCE_EPS || rv1 <= VARIANCE_EPS {
+            return None;
+        }
+        Some((rv0 * rv1).sqrt())
+    }
+
+    fn correlation_of(&self, state: &LagState) -> Option<f64> {
+        Some(state.covariance / self.correlation_denominator()?)
+    }
+}
@@ -0,0 +1,90 @@
+use numpy::PyReadonlyArray1;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// Misra-Gries heavy-hitters sketch over security ids.
+///
+/// Keeps at most `capacity` counters; any id whose true frequency exceeds
+/// `total / (capacity + 1)` is guaranteed to be tracked, and reported counts
+/// underestimate the truth by at most that amount.
+#[pyclass]
+pub struct RustLossyCounter {
+    capacity: usize,
+    counters: HashMap<u64, u64>,
+    total: u64,
+}
+
+#[pymethods]
+impl RustLossyCounter {
+    #[new]
+    #[pyo3(text_signature = "(capacity)")]
+    pub fn new(capacity: usize) -> PyResult<Self> {
+        if capacity == 0 {
+            return Err(PyValueError::new_err("capacity must be >= 1"));
+        }
+        Ok(Self {
+            capacity,
+            counters: HashMap::with_capacity(capacity + 1),
+            total: 0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.counters.clear();
+        self.total = 0;
+    }
+
+    pub fn update(&mut self, sec_id: u64) {
+        self.total += 1;
+        if let Some(count) = self.counters.get_mut(&sec_id) {
+            *count += 1;
+            return;
+        }
+        if self.counters.len() < self.capacity {
+            self.counters.insert(sec_id, 1);
+            return;
+        }
+        // No free counter: decrement everything and drop the ones that hit zero.
+        self.counters.retain(|_, count| {
+            *count -= 1;
+            *count > 0
+        });
+    }
+
+    pub fn update_many<'py>(&mut self, sec_ids: PyReadonlyArray1<'py, u64>) -> PyResult<()> {
+        for &sec_id in sec_ids.as_slice()? {
+            self.update(sec_id);
+        }
+        Ok(())
+    }
+
+    /// Up to `k` tracked ids with their estimated counts, most frequent first.
+    pub fn top_k(&self, k: usize) -> Vec<(u64, u64)> {
+        let mut entries: Vec<(u64, u64)> = self
+            .counters
+            .iter()
+            .map(|(&sec_id, &count)| (sec_id, count))
+            .collect();
+        entries.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        entries.truncate(k);
+        entries
+    }
+
+    pub fn estimate(&self, sec_id: u64) -> u64 {
+        self.counters.get(&sec_id).copied().unwrap_or(0)
+    }
+
+    /// Maximum amount by which any reported count may undercount the truth.
+    pub fn error_bound(&self) -> u64 {
+        self.total / (self.capacity as u64 + 1)
+    }
+
+    pub fn total_count(&self) -> u64 {
+        self.total
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
@@ -0,0 +1,241 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+const SIZE_EPS: f64 = 1e-12;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct BookSignals {
+    microprice: f64,
+    imbalance: f64,
+    weighted_mid: f64,
+}
+
+/// Microprice, top-of-book imbalance and N-level weighted mid, each smoothed
+/// with an EMA (`smoothing = 1.0` disables smoothing).
+#[pyclass]
+pub struct RustMicroprice {
+    levels: usize,
+    smoothing: f64,
+    raw: Option<BookSignals>,
+    smoothed: Option<BookSignals>,
+}
+
+#[pymethods]
+impl RustMicroprice {
+    #[new]
+    #[pyo3(signature = (levels = 1, smoothing = 1.0))]
+    pub fn new(levels: usize, smoothing: f64) -> PyResult<Self> {
+        if levels == 0 {
+            return Err(PyValueError::new_err("levels must be >= 1"));
+        }
+        if !smoothing.is_finite() || smoothing <= 0.0 || smoothing > 1.0 {
+            return Err(PyValueError::new_err("smoothing must be in (0, 1]"));
+        }
+        Ok(Self {
+            levels,
+            smoothing,
+            raw: None,
+            smoothed: None,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.raw = None;
+        self.smoothed = None;
+    }
+
+    /// Feed a book snapshot; returns the smoothed microprice, or `None` when
+    /// either side is empty.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_from_levels<'py>(
+        &mut self,
+        bid_prices: PyReadonlyArray1<'py, f64>,
+        bid_sizes: PyReadonlyArray1<'py, f64>,
+        ask_prices: PyReadonlyArray1<'py, f64>,
+        ask_sizes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Option<f64>> {
+        self.step(
+            bid_prices.as_slice()?,
+            bid_sizes.as_slice()?,
+            ask_prices.as_slice()?,
+            ask_sizes.as_slice()?,
+        )
+    }
+
+    /// Top-of-book batch API: element `i` of each array is one quote update.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_top_series<'py>(
+        &mut self,
+        bid_prices: PyReadonlyArray1<'py, f64>,
+        bid_sizes: PyReadonlyArray1<'py, f64>,
+        ask_prices: PyReadonlyArray1<'py, f64>,
+        ask_sizes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        self.top_series(
+            bid_prices.as_slice()?,
+            bid_sizes.as_slice()?,
+            ask_prices.as_slice()?,
+            ask_sizes.as_slice()?,
+            |value| value,
+        )
+    }
+
+    /// `update_top_series` returning a float64 array with NaN for one-sided books.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_top_series_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        bid_prices: PyReadonlyArray1<'py, f64>,
+        bid_sizes: PyReadonlyArray1<'py, f64>,
+        ask_prices: PyReadonlyArray1<'py, f64>,
+        ask_sizes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out = self.top_series(
+            bid_prices.as_slice()?,
+            bid_sizes.as_slice()?,
+            ask_prices.as_slice()?,
+            ask_sizes.as_slice()?,
+            |value| value.unwrap_or(f64::NAN),
+        )?;
+        Ok(out.into_pyarray(py))
+    }
+
+    pub fn microprice(&self) -> Option<f64> {
+        self.smoothed.map(|s| s.microprice)
+    }
+
+    /// `(bid_size - ask_size) / (bid_size + ask_size)` at the top of book.
+    pub fn imbalance(&self) -> Option<f64> {
+        self.smoothed.map(|s| s.imbalance)
+    }
+
+    /// Microprice generalised to the top `levels`: side VWAPs weighted by the
+    /// opposite side's total size.
+    pub fn weighted_mid(&self) -> Option<f64> {
+        self.smoothed.map(|s| s.weighted_mid)
+    }
+
+    /// Unsmoothed `(microprice, imbalance, weighted_mid)` from the last snapshot.
+    pub fn raw_values(&self) -> Option<(f64, f64, f64)> {
+        self.raw
+            .map(|s| (s.microprice, s.imbalance, s.weighted_mid))
+    }
+
+    pub fn levels(&self) -> usize {
+        self.levels
+    }
+}
+
+impl RustMicroprice {
+    fn top_series<T>(
+        &mut self,
+        bid_prices: &[f64],
+        bid_sizes: &[f64],
+        ask_prices: &[f64],
+        ask_sizes: &[f64],
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        let n = bid_prices.len();
+        if bid_sizes.len() != n || ask_prices.len() != n || ask_sizes.len() != n {
+            return Err(PyValueError::new_err(
+                "price/size arrays must have matching length",
+            ));
+        }
+        let mut out = Vec::with_capacity(n);
+        for i in 0..n {
+            out.push(emit(self.step(
+                &bid_prices[i..=i],
+                &bid_sizes[i..=i],
+                &ask_prices[i..=i],
+                &ask_sizes[i..=i],
+            )?));
+        }
+        Ok(out)
+    }
+
+    pub fn step(
+        &mut self,
+        bid_prices: &[f64],
+        bid_sizes: &[f64],
+        ask_prices: &[f64],
+        ask_sizes: &[f64],
+    ) -> PyResult<Option<f64>> {
+        if bid_prices.len() != bid_sizes.len() || ask_prices.len() != ask_sizes.len() {
+            return Err(PyValueError::new_err(
+                "price/size arrays must have matching length",
+            ));
+        }
+        if bid_prices.is_empty() || ask_prices.is_empty() {
+            return Ok(None);
+        }
+
+        let raw = self.compute(bid_prices, bid_sizes, ask_prices, ask_sizes);
+        let smoothed = match self.smoothed {
+            Some(prev) => {
+                let a = self.smoothing;
+                BookSignals {
+                    microprice: prev.microprice + a * (raw.microprice - prev.microprice),
+                    imbalance: prev.imbalance + a * (raw.imbalance - prev.imbalance),
+                    weighted_mid: prev.weighted_mid + a * (raw.weighted_mid - prev.weighted_mid),
+                }
+            }
+            None => raw,
+        };
+        self.raw = Some(raw);
+        self.smoothed = Some(smoothed);
+        Ok(Some(smoothed.microprice))
+    }
+
+    fn compute(
+        &self,
+        bid_prices: &[f64],
+        bid_sizes: &[f64],
+        ask_prices: &[f64],
+        ask_sizes: &[f64],
+    ) -> BookSignals {
+        let (bid, bid_size) = (bid_prices[0], bid_sizes[0]);
+        let (ask, ask_size) = (ask_prices[0], ask_sizes[0]);
+        let mid = 0.5 * (bid + ask);
+
+        let top_total = bid_size + ask_size;
+        let (microprice, imbalance) = if top_total > SIZE_EPS {
+            (
+                (bid * ask_size + ask * bid_size) / top_total,
+                (bid_size - ask_size) / top_total,
+            )
+        } else {
+            (mid, 0.0)
+        };
+
+        let (bid_vwap, bid_depth) = side_vwap(bid_prices, bid_sizes, self.levels);
+        let (ask_vwap, ask_depth) = side_vwap(ask_prices, ask_sizes, self.levels);
+        let depth_total = bid_depth + ask_depth;
+        let weighted_mid = if depth_total > SIZE_EPS {
+            (bid_vwap * ask_depth + ask_vwap * bid_depth) / depth_total
+        } else {
+            mid
+        };
+
+        BookSignals {
+            microprice,
+            imbalance,
+            weighted_mid,
+        }
+    }
+}
+
+/// Size-weighted average price and total size over the first `levels` entries.
+fn side_vwap(prices: &[f64], sizes: &[f64], levels: usize) -> (f64, f64) {
+    let mut notional = 0.0;
+    let mut depth = 0.0;
+    for (&price, &size) in prices.iter().zip(sizes).take(levels) {
+        notional += price * size;
+        depth += size;
+    }
+    if depth > SIZE_EPS {
+        (notional / depth, depth)
+    } else {
+        (prices[0], 0.0)
+    }
+}
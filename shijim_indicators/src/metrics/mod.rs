@@ -1,3 +1,65 @@
+pub mod acd;
+pub mod bars;
 pub mod hawkes;
+pub mod kyle_lambda;
+pub mod leadlag;
+pub mod lossy_count;
+pub mod microprice;
+pub mod multivariate_hawkes;
 pub mod ofi;
+mod optimize;
+pub mod order_book;
+pub mod pairwise;
+pub mod queue;
+pub mod rolling;
+pub mod rv;
+pub mod spreads;
+pub mod trade_sign;
 pub mod vpin;
+pub mod vwap;
+
+/// Stateful indicators that can be cleared without rebuilding them.
+pub trait Resettable {
+    /// Drop all accumulated state, keeping the configuration.
+    fn reset(&mut self);
+
+    /// Start of a new trading session; a full reset unless the indicator
+    /// carries state across sessions on purpose.
+    fn roll_session(&mut self) {
+        Resettable::reset(self);
+    }
+}
+
+macro_rules! impl_resettable {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Resettable for $ty {
+                fn reset(&mut self) {
+                    <$ty>::reset(self);
+                }
+            }
+        )*
+    };
+}
+
+impl_resettable!(
+    acd::RustAcdModel,
+    bars::RustBarBuilder,
+    bars::RustBarSampler,
+    hawkes::RustHawkesIntensity,
+    kyle_lambda::RustKyleLambda,
+    leadlag::RustHayashiYoshida,
+    lossy_count::RustLossyCounter,
+    microprice::RustMicroprice,
+    multivariate_hawkes::RustMultivariateHawkes,
+    ofi::RustMultiLevelOfi,
+    ofi::RustOfiCalculator,
+    order_book::RustOrderBook,
+    pairwise::RustRollingCorrelation,
+    queue::RustQueueEstimator,
+    rolling::RustRollingStats,
+    rv::RustRealizedVolatility,
+    spreads::RustSpreadMetrics,
+    trade_sign::RustTradeClassifier,
+    vpin::RustVpinCalculator,
+);
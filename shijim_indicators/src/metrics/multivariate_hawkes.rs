@@ -0,0 +1,160 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+const MIN_TIME_EPS: f64 = 1e-12;
+
+/// K-type Hawkes process with exponential kernels and cross-excitation.
+///
+/// `lambda_i(t) = baseline_i + sum_j alpha[i][j] * sum_{t_k of type j} exp(-beta_i (t - t_k))`,
+/// so `alpha[i][j]` is the jump in type `i`'s intensity caused by a type `j` event.
+#[pyclass]
+pub struct RustMultivariateHawkes {
+    baselines: Vec<f64>,
+    alphas: Vec<f64>,
+    betas: Vec<f64>,
+    excitations: Vec<f64>,
+    last_timestamp: Option<f64>,
+}
+
+#[pymethods]
+impl RustMultivariateHawkes {
+    #[new]
+    #[pyo3(text_signature = "(baselines, alphas, betas)")]
+    pub fn new<'py>(
+        baselines: PyReadonlyArray1<'py, f64>,
+        alphas: PyReadonlyArray2<'py, f64>,
+        betas: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Self> {
+        let baselines = baselines.as_slice()?.to_vec();
+        let betas = betas.as_slice()?.to_vec();
+        let dim = baselines.len();
+        if dim == 0 {
+            return Err(PyValueError::new_err("at least one event type is required"));
+        }
+        let alpha_view = alphas.as_array();
+        if alpha_view.shape() != [dim, dim] || betas.len() != dim {
+            return Err(PyValueError::new_err(
+                "alphas must be K x K and betas length K for K baselines",
+            ));
+        }
+        if baselines.iter().any(|b| !b.is_finite() || *b < 0.0) {
+            return Err(PyValueError::new_err(
+                "baseline intensities must be finite and >= 0",
+            ));
+        }
+        if alpha_view.iter().any(|a| !a.is_finite() || *a < 0.0) {
+            return Err(PyValueError::new_err("alphas must be finite and >= 0"));
+        }
+        if betas.iter().any(|b| !b.is_finite() || *b <= 0.0) {
+            return Err(PyValueError::new_err("betas must be finite and > 0"));
+        }
+
+        Ok(Self {
+            baselines,
+            alphas: alpha_view.iter().copied().collect(),
+            betas,
+            excitations: vec![0.0; dim],
+            last_timestamp: None,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.excitations.iter_mut().for_each(|e| *e = 0.0);
+        self.last_timestamp = None;
+    }
+
+    /// Register an event of `event_type` at `timestamp`; returns all K intensities
+    /// immediately after the event.
+    pub fn update<'py>(
+        &mut self,
+        py: Python<'py>,
+        event_type: usize,
+        timestamp: f64,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        self.step(event_type, timestamp)?;
+        Ok(self.intensities().into_pyarray(py))
+    }
+
+    pub fn update_many<'py>(
+        &mut self,
+        event_types: PyReadonlyArray1<'py, i64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<()> {
+        let types = event_types.as_slice()?;
+        let times = timestamps.as_slice()?;
+        if types.len() != times.len() {
+            return Err(PyValueError::new_err(
+                "event_types/timestamps arrays must have matching length",
+            ));
+        }
+        for (&event_type, &ts) in types.iter().zip(times) {
+            let event_type = usize::try_from(event_type)
+                .map_err(|_| PyValueError::new_err("event_type must be >= 0"))?;
+            self.step(event_type, ts)?;
+        }
+        Ok(())
+    }
+
+    pub fn current_intensities<'py>(&self, py: Python<'py>) -> &'py PyArray1<f64> {
+        self.intensities().into_pyarray(py)
+    }
+
+    /// Intensities decayed to `timestamp` without registering an event.
+    pub fn intensities_at<'py>(
+        &self,
+        py: Python<'py>,
+        timestamp: f64,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let dt = self.elapsed(timestamp)?;
+        let out: Vec<f64> = (0..self.dimension())
+            .map(|i| self.baselines[i] + self.excitations[i] * (-self.betas[i] * dt).exp())
+            .collect();
+        Ok(out.into_pyarray(py))
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.baselines.len()
+    }
+}
+
+impl RustMultivariateHawkes {
+    pub fn step(&mut self, event_type: usize, timestamp: f64) -> PyResult<()> {
+        let dim = self.dimension();
+        if event_type >= dim {
+            return Err(PyValueError::new_err(format!(
+                "event_type must be in [0, {dim})"
+            )));
+        }
+        let dt = self.elapsed(timestamp)?;
+        for i in 0..dim {
+            let decayed = self.excitations[i] * (-self.betas[i] * dt).exp();
+            self.excitations[i] = decayed + self.alphas[i * dim + event_type];
+        }
+        self.last_timestamp = Some(timestamp);
+        Ok(())
+    }
+
+    fn intensities(&self) -> Vec<f64> {
+        self.baselines
+            .iter()
+            .zip(&self.excitations)
+            .map(|(b, e)| b + e)
+            .collect()
+    }
+
+    fn elapsed(&self, timestamp: f64) -> PyResult<f64> {
+        if !timestamp.is_finite() {
+            return Err(PyValueError::new_err(
+                "timestamps supplied to Hawkes calculator must be finite",
+            ));
+        }
+        match self.last_timestamp {
+            Some(last_ts) if timestamp + MIN_TIME_EPS < last_ts => Err(PyValueError::new_err(
+                "timestamps must be non-decreasing for Hawkes updates",
+            )),
+            Some(last_ts) => Ok((timestamp - last_ts).max(0.0)),
+            None => Ok(0.0),
+        }
+    }
+}
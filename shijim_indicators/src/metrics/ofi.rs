@@ -1,4 +1,4 @@
-use numpy::PyReadonlyArray1;
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1, PyReadonlyArray2};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
@@ -31,60 +31,156 @@ impl RustOfiCalculator {
         ask_prices: PyReadonlyArray1<'py, f64>,
         ask_sizes: PyReadonlyArray1<'py, f64>,
     ) -> PyResult<Option<f64>> {
-        let best_bid = Self::best_level(bid_prices.as_slice()?, bid_sizes.as_slice()?)?;
-        let best_ask = Self::best_level(ask_prices.as_slice()?, ask_sizes.as_slice()?)?;
-
-        if best_bid.is_none() || best_ask.is_none() {
-            // Missing depth data; treat as zero flow and update stored state.
-            self.prev_bid = best_bid;
-            self.prev_ask = best_ask;
-            return Ok(Some(0.0));
+        let update = self.step(
+            bid_prices.as_slice()?,
+            bid_sizes.as_slice()?,
+            ask_prices.as_slice()?,
+            ask_sizes.as_slice()?,
+        )?;
+        Ok(match update {
+            OfiUpdate::WarmingUp => None,
+            // Missing depth data is reported as zero flow for compatibility.
+            OfiUpdate::MissingDepth => Some(0.0),
+            OfiUpdate::Value(value) => Some(value),
+        })
+    }
+
+    /// Same as `update_from_levels`, but returns `(status, value)` where status is
+    /// one of `"warming_up"`, `"missing_depth"` or `"value"`; value is only set for
+    /// the latter.
+    pub fn update_with_status<'py>(
+        &mut self,
+        bid_prices: PyReadonlyArray1<'py, f64>,
+        bid_sizes: PyReadonlyArray1<'py, f64>,
+        ask_prices: PyReadonlyArray1<'py, f64>,
+        ask_sizes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<(&'static str, Option<f64>)> {
+        let update = self.step(
+            bid_prices.as_slice()?,
+            bid_sizes.as_slice()?,
+            ask_prices.as_slice()?,
+            ask_sizes.as_slice()?,
+        )?;
+        Ok((update.status(), update.value()))
+    }
+
+    /// Batch `update_from_levels` over `(n_snapshots, n_levels)` arrays (bid and
+    /// ask level counts may differ). Returns one OFI per snapshot, NaN while
+    /// warming up; arrays must be C-contiguous.
+    pub fn update_series<'py>(
+        &mut self,
+        py: Python<'py>,
+        bid_prices: PyReadonlyArray2<'py, f64>,
+        bid_sizes: PyReadonlyArray2<'py, f64>,
+        ask_prices: PyReadonlyArray2<'py, f64>,
+        ask_sizes: PyReadonlyArray2<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let bid_shape = bid_prices.shape();
+        let ask_shape = ask_prices.shape();
+        if bid_shape != bid_sizes.shape()
+            || ask_shape != ask_sizes.shape()
+            || bid_shape[0] != ask_shape[0]
+        {
+            return Err(PyValueError::new_err(
+                "bid/ask price and size arrays must share shapes and snapshot count",
+            ));
         }
+        let (n_snapshots, bid_levels, ask_levels) = (bid_shape[0], bid_shape[1], ask_shape[1]);
+        let bid_prices = bid_prices.as_slice()?;
+        let bid_sizes = bid_sizes.as_slice()?;
+        let ask_prices = ask_prices.as_slice()?;
+        let ask_sizes = ask_sizes.as_slice()?;
 
-        let bid = best_bid.unwrap();
-        let ask = best_ask.unwrap();
+        let mut out = Vec::with_capacity(n_snapshots);
+        for row in 0..n_snapshots {
+            let bids = row * bid_levels..(row + 1) * bid_levels;
+            let asks = row * ask_levels..(row + 1) * ask_levels;
+            let update = self.step(
+                &bid_prices[bids.clone()],
+                &bid_sizes[bids],
+                &ask_prices[asks.clone()],
+                &ask_sizes[asks],
+            )?;
+            out.push(match update {
+                OfiUpdate::WarmingUp => f64::NAN,
+                OfiUpdate::MissingDepth => 0.0,
+                OfiUpdate::Value(value) => value,
+            });
+        }
+        Ok(out.into_pyarray(py))
+    }
+}
 
-        let prev_bid = match self.prev_bid {
-            Some(prev) => prev,
-            None => {
-                self.prev_bid = Some(bid);
-                self.prev_ask = Some(ask);
-                return Ok(None);
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OfiUpdate {
+    /// No previous book to diff against yet.
+    WarmingUp,
+    /// One side of the book had no levels.
+    MissingDepth,
+    Value(f64),
+}
+
+impl OfiUpdate {
+    pub fn status(&self) -> &'static str {
+        match self {
+            OfiUpdate::WarmingUp => "warming_up",
+            OfiUpdate::MissingDepth => "missing_depth",
+            OfiUpdate::Value(_) => "value",
+        }
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            OfiUpdate::Value(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl Default for RustOfiCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RustOfiCalculator {
+    pub fn step(
+        &mut self,
+        bid_prices: &[f64],
+        bid_sizes: &[f64],
+        ask_prices: &[f64],
+        ask_sizes: &[f64],
+    ) -> PyResult<OfiUpdate> {
+        let best_bid = Self::best_level(bid_prices, bid_sizes)?;
+        let best_ask = Self::best_level(ask_prices, ask_sizes)?;
+
+        let (bid, ask) = match (best_bid, best_ask) {
+            (Some(bid), Some(ask)) => (bid, ask),
+            _ => {
+                // Missing depth data; update stored state so the next book diffs against it.
+                self.prev_bid = best_bid;
+                self.prev_ask = best_ask;
+                return Ok(OfiUpdate::MissingDepth);
             }
         };
-        let prev_ask = match self.prev_ask {
-            Some(prev) => prev,
-            None => {
+
+        let (prev_bid, prev_ask) = match (self.prev_bid, self.prev_ask) {
+            (Some(prev_bid), Some(prev_ask)) => (prev_bid, prev_ask),
+            _ => {
                 self.prev_bid = Some(bid);
                 self.prev_ask = Some(ask);
-                return Ok(None);
+                return Ok(OfiUpdate::WarmingUp);
             }
         };
 
-        let bid_contrib = if bid.0 > prev_bid.0 {
-            bid.1
-        } else if bid.0 < prev_bid.0 {
-            -prev_bid.1
-        } else {
-            bid.1 - prev_bid.1
-        };
-
-        let ask_contrib = if ask.0 < prev_ask.0 {
-            ask.1
-        } else if ask.0 > prev_ask.0 {
-            -prev_ask.1
-        } else {
-            ask.1 - prev_ask.1
-        };
+        let flow = bid_flow(bid, prev_bid) - ask_flow(ask, prev_ask);
 
         self.prev_bid = Some(bid);
         self.prev_ask = Some(ask);
 
-        Ok(Some(bid_contrib - ask_contrib))
+        Ok(OfiUpdate::Value(flow))
     }
-}
 
-impl RustOfiCalculator {
     fn best_level(prices: &[f64], sizes: &[f64]) -> PyResult<Option<(f64, f64)>> {
         if prices.is_empty() || sizes.is_empty() {
             return Ok(None);
@@ -97,3 +193,168 @@ impl RustOfiCalculator {
         Ok(Some((prices[0], sizes[0])))
     }
 }
+
+/// Bid-side order flow between two `(price, size)` levels.
+fn bid_flow(bid: (f64, f64), prev_bid: (f64, f64)) -> f64 {
+    if bid.0 > prev_bid.0 {
+        bid.1
+    } else if bid.0 < prev_bid.0 {
+        -prev_bid.1
+    } else {
+        bid.1 - prev_bid.1
+    }
+}
+
+/// Ask-side order flow between two `(price, size)` levels.
+fn ask_flow(ask: (f64, f64), prev_ask: (f64, f64)) -> f64 {
+    if ask.0 < prev_ask.0 {
+        ask.1
+    } else if ask.0 > prev_ask.0 {
+        -prev_ask.1
+    } else {
+        ask.1 - prev_ask.1
+    }
+}
+
+/// Depth-weighted multi-level OFI (Cont et al.) over the top `depth` levels.
+///
+/// Level `i` (0 = best) contributes its own bid/ask flow scaled by `weights[i]`,
+/// which defaults to `decay ** i`.
+#[pyclass]
+pub struct RustMultiLevelOfi {
+    weights: Vec<f64>,
+    prev_bids: Vec<(f64, f64)>,
+    prev_asks: Vec<(f64, f64)>,
+    last_flows: Vec<f64>,
+    warmed_up: bool,
+}
+
+#[pymethods]
+impl RustMultiLevelOfi {
+    #[new]
+    #[pyo3(signature = (depth, decay = 1.0, weights = None))]
+    pub fn new(depth: usize, decay: f64, weights: Option<Vec<f64>>) -> PyResult<Self> {
+        if depth == 0 {
+            return Err(PyValueError::new_err("depth must be >= 1"));
+        }
+        let weights = match weights {
+            Some(weights) => {
+                if weights.len() != depth {
+                    return Err(PyValueError::new_err(
+                        "weights must have exactly `depth` entries",
+                    ));
+                }
+                if weights.iter().any(|w| !w.is_finite()) {
+                    return Err(PyValueError::new_err("weights must be finite"));
+                }
+                weights
+            }
+            None => {
+                if !decay.is_finite() || decay <= 0.0 {
+                    return Err(PyValueError::new_err(
+                        "decay must be a positive, finite number",
+                    ));
+                }
+                (0..depth).map(|level| decay.powi(level as i32)).collect()
+            }
+        };
+        Ok(Self {
+            weights,
+            prev_bids: Vec::with_capacity(depth),
+            prev_asks: Vec::with_capacity(depth),
+            last_flows: vec![0.0; depth],
+            warmed_up: false,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_bids.clear();
+        self.prev_asks.clear();
+        self.last_flows.iter_mut().for_each(|flow| *flow = 0.0);
+        self.warmed_up = false;
+    }
+
+    /// Weighted OFI across levels; `None` until a previous book is available.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_from_levels<'py>(
+        &mut self,
+        bid_prices: PyReadonlyArray1<'py, f64>,
+        bid_sizes: PyReadonlyArray1<'py, f64>,
+        ask_prices: PyReadonlyArray1<'py, f64>,
+        ask_sizes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Option<f64>> {
+        self.step(
+            bid_prices.as_slice()?,
+            bid_sizes.as_slice()?,
+            ask_prices.as_slice()?,
+            ask_sizes.as_slice()?,
+        )
+    }
+
+    /// Unweighted per-level flows from the most recent update.
+    pub fn level_flows<'py>(&self, py: Python<'py>) -> &'py PyArray1<f64> {
+        self.last_flows.clone().into_pyarray(py)
+    }
+
+    pub fn weights(&self) -> Vec<f64> {
+        self.weights.clone()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.weights.len()
+    }
+}
+
+impl RustMultiLevelOfi {
+    pub fn step(
+        &mut self,
+        bid_prices: &[f64],
+        bid_sizes: &[f64],
+        ask_prices: &[f64],
+        ask_sizes: &[f64],
+    ) -> PyResult<Option<f64>> {
+        let depth = self.weights.len();
+        let bids = Self::top_levels(bid_prices, bid_sizes, depth)?;
+        let asks = Self::top_levels(ask_prices, ask_sizes, depth)?;
+
+        if !self.warmed_up {
+            self.prev_bids = bids;
+            self.prev_asks = asks;
+            self.warmed_up = true;
+            return Ok(None);
+        }
+
+        let mut total = 0.0;
+        for (level, flow_slot) in self.last_flows.iter_mut().enumerate() {
+            // Levels missing from either book contribute no flow.
+            let bid = match (bids.get(level), self.prev_bids.get(level)) {
+                (Some(&bid), Some(&prev)) => bid_flow(bid, prev),
+                _ => 0.0,
+            };
+            let ask = match (asks.get(level), self.prev_asks.get(level)) {
+                (Some(&ask), Some(&prev)) => ask_flow(ask, prev),
+                _ => 0.0,
+            };
+            *flow_slot = bid - ask;
+            total += self.weights[level] * *flow_slot;
+        }
+
+        self.prev_bids = bids;
+        self.prev_asks = asks;
+        Ok(Some(total))
+    }
+
+    fn top_levels(prices: &[f64], sizes: &[f64], depth: usize) -> PyResult<Vec<(f64, f64)>> {
+        if prices.len() != sizes.len() {
+            return Err(PyValueError::new_err(
+                "price/size arrays must have matching length",
+            ));
+        }
+        Ok(prices
+            .iter()
+            .zip(sizes)
+            .take(depth)
+            .map(|(&price, &size)| (price, size))
+            .collect())
+    }
+}
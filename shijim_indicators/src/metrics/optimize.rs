@@ -0,0 +1,88 @@
+/// Derivative-free Nelder-Mead minimisation.
+///
+/// Returns the best point found and its objective value. The objective may
+/// return `f64::INFINITY` to reject infeasible points.
+pub(crate) fn nelder_mead<F>(
+    objective: F,
+    start: &[f64],
+    step: f64,
+    max_iter: usize,
+    tol: f64,
+) -> (Vec<f64>, f64)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    const REFLECT: f64 = 1.0;
+    const EXPAND: f64 = 2.0;
+    const CONTRACT: f64 = 0.5;
+    const SHRINK: f64 = 0.5;
+
+    let dim = start.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(dim + 1);
+    simplex.push((start.to_vec(), objective(start)));
+    for axis in 0..dim {
+        let mut point = start.to_vec();
+        point[axis] += step;
+        let value = objective(&point);
+        simplex.push((point, value));
+    }
+
+    for _ in 0..max_iter {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let best = simplex[0].1;
+        let worst = simplex[dim].1;
+        if best.is_finite() && (worst - best).abs() <= tol * (1.0 + best.abs()) {
+            break;
+        }
+
+        let mut centroid = vec![0.0; dim];
+        for (point, _) in &simplex[..dim] {
+            for (c, x) in centroid.iter_mut().zip(point) {
+                *c += x / dim as f64;
+            }
+        }
+        let along = |scale: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(&simplex[dim].0)
+                .map(|(c, w)| c + scale * (c - w))
+                .collect()
+        };
+
+        let reflected = along(REFLECT);
+        let reflected_value = objective(&reflected);
+        if reflected_value < simplex[0].1 {
+            let expanded = along(EXPAND);
+            let expanded_value = objective(&expanded);
+            simplex[dim] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+            continue;
+        }
+        if reflected_value < simplex[dim - 1].1 {
+            simplex[dim] = (reflected, reflected_value);
+            continue;
+        }
+
+        let contracted = along(-CONTRACT);
+        let contracted_value = objective(&contracted);
+        if contracted_value < simplex[dim].1 {
+            simplex[dim] = (contracted, contracted_value);
+            continue;
+        }
+
+        let anchor = simplex[0].0.clone();
+        for (point, value) in simplex.iter_mut().skip(1) {
+            for (x, a) in point.iter_mut().zip(&anchor) {
+                *x = a + SHRINK * (*x - a);
+            }
+            *value = objective(point);
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (point, value) = simplex.swap_remove(0);
+    (point, value)
+}
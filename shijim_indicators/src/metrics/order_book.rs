@@ -0,0 +1,248 @@
+use numpy::{IntoPyArray, PyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+const SIZE_EPS: f64 = 1e-12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+impl Side {
+    pub fn parse(side: &str) -> PyResult<Self> {
+        match side {
+            "bid" | "buy" | "B" => Ok(Self::Bid),
+            "ask" | "sell" | "S" => Ok(Self::Ask),
+            _ => Err(PyValueError::new_err(
+                "side must be one of 'bid', 'buy', 'B', 'ask', 'sell' or 'S'",
+            )),
+        }
+    }
+}
+
+/// Aggregated price-level book keyed by integer ticks; reported prices are
+/// tick multiples.
+#[pyclass]
+pub struct RustOrderBook {
+    tick_size: f64,
+    bids: BTreeMap<i64, f64>,
+    asks: BTreeMap<i64, f64>,
+}
+
+#[pymethods]
+impl RustOrderBook {
+    #[new]
+    #[pyo3(text_signature = "(tick_size)")]
+    pub fn new(tick_size: f64) -> PyResult<Self> {
+        if !tick_size.is_finite() || tick_size <= 0.0 {
+            return Err(PyValueError::new_err(
+                "tick_size must be a positive, finite number",
+            ));
+        }
+        Ok(Self {
+            tick_size,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    /// Add resting size at a price level; the size must be positive.
+    pub fn add(&mut self, side: &str, price: f64, size: f64) -> PyResult<()> {
+        let side = Side::parse(side)?;
+        let key = self.price_key(price)?;
+        Self::validate_size(size)?;
+        if size <= SIZE_EPS {
+            return Err(PyValueError::new_err("added size must be positive"));
+        }
+        let level = self.levels_mut(side).entry(key).or_insert(0.0);
+        *level += size;
+        Ok(())
+    }
+
+    /// Replace the resting size at a price level; zero removes the level.
+    pub fn modify(&mut self, side: &str, price: f64, size: f64) -> PyResult<()> {
+        let side = Side::parse(side)?;
+        let key = self.price_key(price)?;
+        Self::validate_size(size)?;
+        let levels = self.levels_mut(side);
+        if size <= SIZE_EPS {
+            levels.remove(&key);
+        } else {
+            levels.insert(key, size);
+        }
+        Ok(())
+    }
+
+    /// Remove a price level entirely.
+    pub fn delete(&mut self, side: &str, price: f64) -> PyResult<()> {
+        let side = Side::parse(side)?;
+        let key = self.price_key(price)?;
+        self.levels_mut(side).remove(&key);
+        Ok(())
+    }
+
+    /// Consume `size` from the resting liquidity on `side` at `price`.
+    pub fn trade(&mut self, side: &str, price: f64, size: f64) -> PyResult<()> {
+        let side = Side::parse(side)?;
+        let key = self.price_key(price)?;
+        Self::validate_size(size)?;
+        self.reduce_level(side, key, size);
+        Ok(())
+    }
+
+    pub fn best_bid(&self) -> Option<(f64, f64)> {
+        self.bids
+            .iter()
+            .next_back()
+            .map(|(&key, &size)| (self.key_price(key), size))
+    }
+
+    pub fn best_ask(&self) -> Option<(f64, f64)> {
+        self.asks
+            .iter()
+            .next()
+            .map(|(&key, &size)| (self.key_price(key), size))
+    }
+
+    pub fn mid_price(&self) -> Option<f64> {
+        let (bid, _) = self.best_bid()?;
+        let (ask, _) = self.best_ask()?;
+        Some(0.5 * (bid + ask))
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        let (bid, _) = self.best_bid()?;
+        let (ask, _) = self.best_ask()?;
+        Some(ask - bid)
+    }
+
+    /// Size-weighted mid: leans towards the side with less resting size.
+    pub fn microprice(&self) -> Option<f64> {
+        let (bid, bid_size) = self.best_bid()?;
+        let (ask, ask_size) = self.best_ask()?;
+        let total = bid_size + ask_size;
+        if total <= SIZE_EPS {
+            return Some(0.5 * (bid + ask));
+        }
+        Some((bid * ask_size + ask * bid_size) / total)
+    }
+
+    /// `(bid_volume - ask_volume) / (bid_volume + ask_volume)` over the top `levels`.
+    #[pyo3(signature = (levels = 1))]
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let bid_volume: f64 = self.bids.values().rev().take(levels).sum();
+        let ask_volume: f64 = self.asks.values().take(levels).sum();
+        let total = bid_volume + ask_volume;
+        if total <= SIZE_EPS {
+            return None;
+        }
+        Some((bid_volume - ask_volume) / total)
+    }
+
+    /// Top `levels` of each side as `(bid_prices, bid_sizes, ask_prices, ask_sizes)`,
+    /// best level first, ready to feed into `RustOfiCalculator.update_from_levels`.
+    #[allow(clippy::type_complexity)]
+    pub fn depth<'py>(
+        &self,
+        py: Python<'py>,
+        levels: usize,
+    ) -> (
+        &'py PyArray1<f64>,
+        &'py PyArray1<f64>,
+        &'py PyArray1<f64>,
+        &'py PyArray1<f64>,
+    ) {
+        let (bid_prices, bid_sizes) = self.side_depth(Side::Bid, levels);
+        let (ask_prices, ask_sizes) = self.side_depth(Side::Ask, levels);
+        (
+            bid_prices.into_pyarray(py),
+            bid_sizes.into_pyarray(py),
+            ask_prices.into_pyarray(py),
+            ask_sizes.into_pyarray(py),
+        )
+    }
+
+    pub fn level_count(&self, side: &str) -> PyResult<usize> {
+        Ok(self.levels(Side::parse(side)?).len())
+    }
+
+    pub fn tick_size(&self) -> f64 {
+        self.tick_size
+    }
+}
+
+impl RustOrderBook {
+    pub fn side_depth(&self, side: Side, levels: usize) -> (Vec<f64>, Vec<f64>) {
+        let mut prices = Vec::with_capacity(levels);
+        let mut sizes = Vec::with_capacity(levels);
+        let mut push = |(&key, &size): (&i64, &f64)| {
+            prices.push(self.key_price(key));
+            sizes.push(size);
+        };
+        match side {
+            Side::Bid => self.bids.iter().rev().take(levels).for_each(&mut push),
+            Side::Ask => self.asks.iter().take(levels).for_each(&mut push),
+        }
+        (prices, sizes)
+    }
+
+    fn reduce_level(&mut self, side: Side, key: i64, size: f64) {
+        let levels = self.levels_mut(side);
+        if let Some(level) = levels.get_mut(&key) {
+            *level -= size;
+            if *level <= SIZE_EPS {
+                levels.remove(&key);
+            }
+        }
+    }
+
+    fn levels(&self, side: Side) -> &BTreeMap<i64, f64> {
+        match side {
+            Side::Bid => &self.bids,
+            Side::Ask => &self.asks,
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut BTreeMap<i64, f64> {
+        match side {
+            Side::Bid => &mut self.bids,
+            Side::Ask => &mut self.asks,
+        }
+    }
+
+    /// Nearest tick index of `price`; zero and negative prices (spreads,
+    /// energy futures) are valid levels.
+    fn price_key(&self, price: f64) -> PyResult<i64> {
+        if !price.is_finite() {
+            return Err(PyValueError::new_err("price must be finite"));
+        }
+        let ticks = (price / self.tick_size).round();
+        if ticks < i64::MIN as f64 || ticks >= i64::MAX as f64 {
+            return Err(PyValueError::new_err(
+                "price is out of range for the tick size",
+            ));
+        }
+        Ok(ticks as i64)
+    }
+
+    /// Price of a tick index: a multiple of `tick_size`, so off-tick prices
+    /// come back snapped to the level they were stored under.
+    fn key_price(&self, key: i64) -> f64 {
+        key as f64 * self.tick_size
+    }
+
+    fn validate_size(size: f64) -> PyResult<()> {
+        if !size.is_finite() || size < 0.0 {
+            return Err(PyValueError::new_err("size must be finite and >= 0"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+const VARIANCE_EPS: f64 = 1e-12;
+/// Variances below this fraction of the raw second moment are treated as zero.
+const RELATIVE_VARIANCE_EPS: f64 = 1e-12;
+const MIN_TIME_EPS: f64 = 1e-12;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Window {
+    Count(usize),
+    Duration(f64),
+}
+
+/// Rolling Pearson correlation and OLS beta of `y` on `x` for two synchronized
+/// streams, over either the last `window_size` pairs or the pairs observed in
+/// the trailing `window_duration`.
+///
+/// Means and co-moments are kept with Welford add/remove updates so price-level
+/// inputs do not lose precision to cancellation.
+#[pyclass]
+pub struct RustRollingCorrelation {
+    window: Window,
+    samples: VecDeque<(f64, f64, f64)>,
+    last_timestamp: Option<f64>,
+    mean_x: f64,
+    mean_y: f64,
+    m2_x: f64,
+    m2_y: f64,
+    c_xy: f64,
+}
+
+#[pymethods]
+impl RustRollingCorrelation {
+    #[new]
+    #[pyo3(signature = (window_size = None, window_duration = None))]
+    pub fn new(window_size: Option<usize>, window_duration: Option<f64>) -> PyResult<Self> {
+        let window = match (window_size, window_duration) {
+            (Some(size), None) => {
+                if size < 2 {
+                    return Err(PyValueError::new_err("window_size must be >= 2"));
+                }
+                Window::Count(size)
+            }
+            (None, Some(duration)) => {
+                if !duration.is_finite() || duration <= 0.0 {
+                    return Err(PyValueError::new_err(
+                        "window_duration must be a positive, finite number",
+                    ));
+                }
+                Window::Duration(duration)
+            }
+            _ => {
+                return Err(PyValueError::new_err(
+                    "exactly one of window_size or window_duration is required",
+                ))
+            }
+        };
+        Ok(Self {
+            window,
+            samples: VecDeque::new(),
+            last_timestamp: None,
+            mean_x: 0.0,
+            mean_y: 0.0,
+            m2_x: 0.0,
+            m2_y: 0.0,
+            c_xy: 0.0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.last_timestamp = None;
+        self.clear_moments();
+    }
+
+    /// Push one synchronized `(x, y)` pair; returns the rolling correlation once
+    /// available. `timestamp` is required for time windows.
+    #[pyo3(signature = (x, y, timestamp = None))]
+    pub fn update(&mut self, x: f64, y: f64, timestamp: Option<f64>) -> PyResult<Option<f64>> {
+        if !x.is_finite() || !y.is_finite() {
+            return Err(PyValueError::new_err("x and y must be finite floats"));
+        }
+        let timestamp = match (self.window, timestamp) {
+            (Window::Duration(_), None) => {
+                return Err(PyValueError::new_err(
+                    "timestamp is required for time-based windows",
+                ))
+            }
+            (_, Some(ts)) => {
+                if !ts.is_finite() {
+                    return Err(PyValueError::new_err("timestamp must be a finite float"));
+                }
+                if self
+                    .last_timestamp
+                    .is_some_and(|last_ts| ts + MIN_TIME_EPS < last_ts)
+                {
+                    return Err(PyValueError::new_err(
+                        "timestamps must be non-decreasing for rolling correlation",
+                    ));
+                }
+                self.last_timestamp = Some(ts);
+                ts
+            }
+            (Window::Count(_), None) => 0.0,
+        };
+        self.push_sample(x, y, timestamp);
+        Ok(self.correlation())
+    }
+
+    #[pyo3(signature = (xs, ys, timestamps = None))]
+    pub fn update_many<'py>(
+        &mut self,
+        xs: PyReadonlyArray1<'py, f64>,
+        ys: PyReadonlyArray1<'py, f64>,
+        timestamps: Option<PyReadonlyArray1<'py, f64>>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        let timestamps = match timestamps.as_ref() {
+            Some(ts) => Some(ts.as_slice()?),
+            None => None,
+        };
+        self.series(xs.as_slice()?, ys.as_slice()?, timestamps, |corr| corr)
+    }
+
+    /// `update_many` returning a float64 array with NaN where no correlation exists.
+    #[pyo3(signature = (xs, ys, timestamps = None))]
+    pub fn update_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        xs: PyReadonlyArray1<'py, f64>,
+        ys: PyReadonlyArray1<'py, f64>,
+        timestamps: Option<PyReadonlyArray1<'py, f64>>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let timestamps = match timestamps.as_ref() {
+            Some(ts) => Some(ts.as_slice()?),
+            None => None,
+        };
+        let out = self.series(xs.as_slice()?, ys.as_slice()?, timestamps, |corr| {
+            corr.unwrap_or(f64::NAN)
+        })?;
+        Ok(out.into_pyarray(py))
+    }
+
+    pub fn correlation(&self) -> Option<f64> {
+        let (sxx, syy, sxy) = self.centered_sums()?;
+        if self.is_flat(sxx, self.mean_x) || self.is_flat(syy, self.mean_y) {
+            return None;
+        }
+        Some((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+    }
+
+    /// OLS slope of `y` on `x`.
+    pub fn beta(&self) -> Option<f64> {
+        let (sxx, _, sxy) = self.centered_sums()?;
+        if self.is_flat(sxx, self.mean_x) {
+            return None;
+        }
+        Some(sxy / sxx)
+    }
+
+    pub fn intercept(&self) -> Option<f64> {
+        let beta = self.beta()?;
+        Some(self.mean_y - beta * self.mean_x)
+    }
+
+    /// Sample covariance (n - 1 denominator).
+    pub fn covariance(&self) -> Option<f64> {
+        let (_, _, sxy) = self.centered_sums()?;
+        Some(sxy / (self.samples.len() - 1) as f64)
+    }
+
+    pub fn samples_ready(&self) -> usize {
+        self.samples.len()
+    }
+}
+
+impl RustRollingCorrelation {
+    fn series<T>(
+        &mut self,
+        xs: &[f64],
+        ys: &[f64],
+        timestamps: Option<&[f64]>,
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        if xs.len() != ys.len() || timestamps.is_some_and(|ts| ts.len() != xs.len()) {
+            return Err(PyValueError::new_err(
+                "xs/ys/timestamps arrays must have matching length",
+            ));
+        }
+        let mut out = Vec::with_capacity(xs.len());
+        for (i, (&x, &y)) in xs.iter().zip(ys).enumerate() {
+            out.push(emit(self.update(x, y, timestamps.map(|ts| ts[i]))?));
+        }
+        Ok(out)
+    }
+
+    fn is_ready(&self) -> bool {
+        match self.window {
+            Window::Count(size) => self.samples.len() >= size,
+            Window::Duration(_) => self.samples.len() >= 2,
+        }
+    }
+
+    /// Centered sums of squares and cross-products over the window.
+    fn centered_sums(&self) -> Option<(f64, f64, f64)> {
+        if !self.is_ready() {
+            return None;
+        }
+        Some((self.m2_x.max(0.0), self.m2_y.max(0.0), self.c_xy))
+    }
+
+    /// Whether a centered sum of squares is negligible next to the raw second
+    /// moment of the same series.
+    fn is_flat(&self, centered: f64, mean: f64) -> bool {
+        let raw = centered + self.samples.len() as f64 * mean * mean;
+        centered <= VARIANCE_EPS.max(RELATIVE_VARIANCE_EPS * raw)
+    }
+
+    fn clear_moments(&mut self) {
+        self.mean_x = 0.0;
+        self.mean_y = 0.0;
+        self.m2_x = 0.0;
+        self.m2_y = 0.0;
+        self.c_xy = 0.0;
+    }
+
+    fn push_sample(&mut self, x: f64, y: f64, timestamp: f64) {
+        // Welford add, then remove expired pairs.
+        self.samples.push_back((timestamp, x, y));
+        let n = self.samples.len() as f64;
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.mean_x += dx / n;
+        self.mean_y += dy / n;
+        self.m2_x += dx * (x - self.mean_x);
+        self.m2_y += dy * (y - self.mean_y);
+        self.c_xy += dx * (y - self.mean_y);
+        loop {
+            let expired = match (self.window, self.samples.front()) {
+                (Window::Count(size), Some(_)) => self.samples.len() > size,
+                (Window::Duration(duration), Some(&(ts, _, _))) => {
+                    ts + duration + MIN_TIME_EPS < timestamp
+                }
+                (_, None) => false,
+            };
+            if !expired {
+                break;
+            }
+            if let Some((_, old_x, old_y)) = self.samples.pop_front() {
+                if self.samples.is_empty() {
+                    self.clear_moments();
+                    continue;
+                }
+                let n = self.samples.len() as f64;
+                let dx = old_x - self.mean_x;
+                let dy = old_y - self.mean_y;
+                self.mean_x -= dx / n;
+                self.mean_y -= dy / n;
+                self.m2_x -= dx * (old_x - self.mean_x);
+                self.m2_y -= dy * (old_y - self.mean_y);
+                self.c_xy -= dx * (old_y - self.mean_y);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+const SIZE_EPS: f64 = 1e-12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CancelModel {
+    /// Cancels hit the queue ahead of us in proportion to its share of the level.
+    Proportional,
+    /// Cancels come from behind us unless there is not enough volume there.
+    Pessimistic,
+    /// Cancels come from ahead of us first.
+    Optimistic,
+}
+
+/// Estimates the queue position of one resting order at a single price level
+/// from L2 depth changes and trades at that level.
+#[pyclass]
+pub struct RustQueueEstimator {
+    cancel_model: CancelModel,
+    queue_ahead: f64,
+    others_depth: f64,
+    remaining: f64,
+    filled: f64,
+    active: bool,
+}
+
+#[pymethods]
+impl RustQueueEstimator {
+    #[new]
+    #[pyo3(signature = (cancel_model = "proportional"))]
+    pub fn new(cancel_model: &str) -> PyResult<Self> {
+        let cancel_model = match cancel_model {
+            "proportional" => CancelModel::Proportional,
+            "pessimistic" => CancelModel::Pessimistic,
+            "optimistic" => CancelModel::Optimistic,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "cancel_model must be 'proportional', 'pessimistic' or 'optimistic'",
+                ))
+            }
+        };
+        Ok(Self {
+            cancel_model,
+            queue_ahead: 0.0,
+            others_depth: 0.0,
+            remaining: 0.0,
+            filled: 0.0,
+            active: false,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.queue_ahead = 0.0;
+        self.others_depth = 0.0;
+        self.remaining = 0.0;
+        self.filled = 0.0;
+        self.active = false;
+    }
+
+    /// Join the back of a level currently showing `level_depth`.
+    pub fn place(&mut self, level_depth: f64, order_size: f64) -> PyResult<()> {
+        Self::validate_size(level_depth, "level_depth")?;
+        Self::validate_size(order_size, "order_size")?;
+        if order_size <= SIZE_EPS {
+            return Err(PyValueError::new_err("order_size must be > 0"));
+        }
+        self.queue_ahead = level_depth;
+        self.others_depth = level_depth;
+        self.remaining = order_size;
+        self.filled = 0.0;
+        self.active = true;
+        Ok(())
+    }
+
+    /// Executions at our price level; returns the quantity filled on our order.
+    pub fn on_trade(&mut self, size: f64) -> PyResult<f64> {
+        Self::validate_size(size, "size")?;
+        if !self.active {
+            return Ok(0.0);
+        }
+        let ahead = size.min(self.queue_ahead);
+        self.queue_ahead -= ahead;
+        let mut leftover = size - ahead;
+
+        let fill = leftover.min(self.remaining);
+        self.remaining -= fill;
+        self.filled += fill;
+        leftover -= fill;
+
+        // Remaining volume trades through the orders queued behind us.
+        self.others_depth = (self.others_depth - ahead - leftover).max(self.queue_ahead);
+        if self.remaining <= SIZE_EPS {
+            self.remaining = 0.0;
+            self.active = false;
+        }
+        Ok(fill)
+    }
+
+    /// New orders joining the level (always behind us).
+    pub fn on_add(&mut self, size: f64) -> PyResult<()> {
+        Self::validate_size(size, "size")?;
+        self.others_depth += size;
+        Ok(())
+    }
+
+    /// Cancellations at the level by other participants.
+    pub fn on_cancel(&mut self, size: f64) -> PyResult<()> {
+        Self::validate_size(size, "size")?;
+        let size = size.min(self.others_depth);
+        let behind = (self.others_depth - self.queue_ahead).max(0.0);
+        let from_ahead = match self.cancel_model {
+            CancelModel::Proportional if self.others_depth > SIZE_EPS => {
+                size * self.queue_ahead / self.others_depth
+            }
+            CancelModel::Proportional => 0.0,
+            CancelModel::Pessimistic => (size - behind).max(0.0),
+            CancelModel::Optimistic => size.min(self.queue_ahead),
+        };
+        self.queue_ahead = (self.queue_ahead - from_ahead).max(0.0);
+        self.others_depth -= size;
+        Ok(())
+    }
+
+    /// Reconcile with an L2 depth update for the level (which includes our own
+    /// remaining size); increases are treated as adds, decreases as cancels.
+    /// Report trades through `on_trade` before the depth update that reflects them.
+    pub fn on_depth(&mut self, visible_depth: f64) -> PyResult<()> {
+        Self::validate_size(visible_depth, "visible_depth")?;
+        let own = if self.active { self.remaining } else { 0.0 };
+        let others = (visible_depth - own).max(0.0);
+        let delta = others - self.others_depth;
+        if delta > SIZE_EPS {
+            self.on_add(delta)
+        } else if delta < -SIZE_EPS {
+            self.on_cancel(-delta)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn queue_ahead(&self) -> f64 {
+        self.queue_ahead
+    }
+
+    /// Share of the other resting volume that is ahead of us (0 = front of queue).
+    pub fn position_fraction(&self) -> f64 {
+        if self.others_depth <= SIZE_EPS {
+            0.0
+        } else {
+            self.queue_ahead / self.others_depth
+        }
+    }
+
+    pub fn remaining(&self) -> f64 {
+        self.remaining
+    }
+
+    pub fn filled(&self) -> f64 {
+        self.filled
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+impl RustQueueEstimator {
+    fn validate_size(size: f64, name: &str) -> PyResult<()> {
+        if !size.is_finite() || size < 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "{name} must be finite and >= 0"
+            )));
+        }
+        Ok(())
+    }
+}
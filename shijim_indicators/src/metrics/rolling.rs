@@ -0,0 +1,226 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+const VARIANCE_EPS: f64 = 1e-12;
+
+/// O(1) rolling mean/variance/z-score, monotonic-deque min/max and an EWMA over
+/// a fixed count window.
+#[pyclass]
+pub struct RustRollingStats {
+    window_size: usize,
+    ewma_alpha: f64,
+    values: VecDeque<f64>,
+    // Index of the next value, used to expire min/max candidates.
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min_candidates: VecDeque<(u64, f64)>,
+    max_candidates: VecDeque<(u64, f64)>,
+    ewma: Option<f64>,
+    ewm_var: f64,
+}
+
+#[pymethods]
+impl RustRollingStats {
+    #[new]
+    #[pyo3(signature = (window_size, half_life = None))]
+    pub fn new(window_size: usize, half_life: Option<f64>) -> PyResult<Self> {
+        if window_size < 2 {
+            return Err(PyValueError::new_err("window_size must be >= 2"));
+        }
+        // Default EWMA half-life spans half the window.
+        let half_life = half_life.unwrap_or(window_size as f64 / 2.0);
+        if !half_life.is_finite() || half_life <= 0.0 {
+            return Err(PyValueError::new_err(
+                "half_life must be a positive, finite number",
+            ));
+        }
+        Ok(Self {
+            window_size,
+            ewma_alpha: 1.0 - 0.5_f64.powf(1.0 / half_life),
+            values: VecDeque::with_capacity(window_size + 1),
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min_candidates: VecDeque::new(),
+            max_candidates: VecDeque::new(),
+            ewma: None,
+            ewm_var: 0.0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.count = 0;
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.min_candidates.clear();
+        self.max_candidates.clear();
+        self.ewma = None;
+        self.ewm_var = 0.0;
+    }
+
+    /// Push one observation; returns the rolling z-score of that observation once
+    /// the window is full.
+    pub fn update(&mut self, value: f64) -> PyResult<Option<f64>> {
+        if !value.is_finite() {
+            return Err(PyValueError::new_err("value must be a finite float"));
+        }
+        self.push(value);
+        Ok(self.zscore_of(value))
+    }
+
+    pub fn update_many<'py>(
+        &mut self,
+        values: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        self.series(values.as_slice()?, |z| z)
+    }
+
+    /// `update_many` returning a float64 array with NaN where no z-score exists.
+    pub fn update_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        values: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out = self.series(values.as_slice()?, |z| z.unwrap_or(f64::NAN))?;
+        Ok(out.into_pyarray(py))
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        self.is_ready().then_some(self.mean)
+    }
+
+    /// Sample variance (n - 1 denominator) over the window.
+    pub fn variance(&self) -> Option<f64> {
+        if !self.is_ready() {
+            return None;
+        }
+        Some((self.m2 / (self.values.len() - 1) as f64).max(0.0))
+    }
+
+    pub fn std(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Z-score of `value` against the current window (defaults to the latest value).
+    #[pyo3(signature = (value = None))]
+    pub fn zscore(&self, value: Option<f64>) -> Option<f64> {
+        let value = value.or_else(|| self.values.back().copied())?;
+        self.zscore_of(value)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        if !self.is_ready() {
+            return None;
+        }
+        self.min_candidates.front().map(|&(_, v)| v)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        if !self.is_ready() {
+            return None;
+        }
+        self.max_candidates.front().map(|&(_, v)| v)
+    }
+
+    /// Exponentially weighted mean; available from the first observation.
+    pub fn ewma(&self) -> Option<f64> {
+        self.ewma
+    }
+
+    pub fn ewm_std(&self) -> Option<f64> {
+        self.ewma.map(|_| self.ewm_var.max(0.0).sqrt())
+    }
+
+    pub fn ewma_alpha(&self) -> f64 {
+        self.ewma_alpha
+    }
+
+    pub fn samples_ready(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+}
+
+impl RustRollingStats {
+    fn series<T>(&mut self, values: &[f64], emit: impl Fn(Option<f64>) -> T) -> PyResult<Vec<T>> {
+        let mut out = Vec::with_capacity(values.len());
+        for &value in values {
+            out.push(emit(self.update(value)?));
+        }
+        Ok(out)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.values.len() >= self.window_size
+    }
+
+    fn zscore_of(&self, value: f64) -> Option<f64> {
+        let std = self.std()?;
+        if std * std <= VARIANCE_EPS {
+            return None;
+        }
+        Some((value - self.mean) / std)
+    }
+
+    fn push(&mut self, value: f64) {
+        let idx = self.count;
+        self.count += 1;
+
+        // Welford add, then remove the expired observation.
+        self.values.push_back(value);
+        let n = self.values.len() as f64;
+        let delta = value - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (value - self.mean);
+        if self.values.len() > self.window_size {
+            if let Some(old) = self.values.pop_front() {
+                let n = self.values.len() as f64;
+                let delta = old - self.mean;
+                self.mean -= delta / n;
+                self.m2 -= delta * (old - self.mean);
+            }
+        }
+
+        while self.min_candidates.back().is_some_and(|&(_, v)| v >= value) {
+            self.min_candidates.pop_back();
+        }
+        self.min_candidates.push_back((idx, value));
+        while self.max_candidates.back().is_some_and(|&(_, v)| v <= value) {
+            self.max_candidates.pop_back();
+        }
+        self.max_candidates.push_back((idx, value));
+        let oldest = (idx + 1).saturating_sub(self.window_size as u64);
+        while self
+            .min_candidates
+            .front()
+            .is_some_and(|&(i, _)| i < oldest)
+        {
+            self.min_candidates.pop_front();
+        }
+        while self
+            .max_candidates
+            .front()
+            .is_some_and(|&(i, _)| i < oldest)
+        {
+            self.max_candidates.pop_front();
+        }
+
+        match self.ewma {
+            Some(prev) => {
+                let a = self.ewma_alpha;
+                let diff = value - prev;
+                let next = prev + a * diff;
+                self.ewm_var = (1.0 - a) * (self.ewm_var + a * diff * diff);
+                self.ewma = Some(next);
+            }
+            None => self.ewma = Some(value),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+const MIN_TIME_EPS: f64 = 1e-12;
+
+/// Fixed-count rolling mean.
+struct RollingMean {
+    window_size: usize,
+    values: VecDeque<f64>,
+    sum: f64,
+}
+
+impl RollingMean {
+    fn new(window_size: usize) -> Self {
+        Self {
+            window_size,
+            values: VecDeque::with_capacity(window_size + 1),
+            sum: 0.0,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.values.push_back(value);
+        self.sum += value;
+        if self.values.len() > self.window_size {
+            if let Some(old) = self.values.pop_front() {
+                self.sum -= old;
+            }
+        }
+    }
+
+    fn mean(&self) -> Option<f64> {
+        if self.values.is_empty() {
+            return None;
+        }
+        Some(self.sum / self.values.len() as f64)
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.sum = 0.0;
+    }
+}
+
+struct PendingMarkout {
+    deadline: f64,
+    sign: f64,
+    price: f64,
+}
+
+/// Rolling quoted, effective and realized spreads.
+///
+/// Effective spread is `2 * sign * (price - mid)` at trade time; realized spread
+/// uses the mid prevailing `markout_horizon` after the trade instead. Crossed
+/// quotes (`ask < bid`) are skipped and counted rather than moving the mid.
+#[pyclass]
+pub struct RustSpreadMetrics {
+    markout_horizon: f64,
+    relative: bool,
+    mid: Option<f64>,
+    last_timestamp: Option<f64>,
+    quoted: RollingMean,
+    effective: RollingMean,
+    realized: RollingMean,
+    pending: VecDeque<PendingMarkout>,
+    last_realized: Option<f64>,
+    crossed_quotes: usize,
+}
+
+#[pymethods]
+impl RustSpreadMetrics {
+    #[new]
+    #[pyo3(signature = (window_size, markout_horizon, relative = false))]
+    pub fn new(window_size: usize, markout_horizon: f64, relative: bool) -> PyResult<Self> {
+        if window_size == 0 {
+            return Err(PyValueError::new_err("window_size must be >= 1"));
+        }
+        if !markout_horizon.is_finite() || markout_horizon < 0.0 {
+            return Err(PyValueError::new_err(
+                "markout_horizon must be finite and >= 0",
+            ));
+        }
+        Ok(Self {
+            markout_horizon,
+            relative,
+            mid: None,
+            last_timestamp: None,
+            quoted: RollingMean::new(window_size),
+            effective: RollingMean::new(window_size),
+            realized: RollingMean::new(window_size),
+            pending: VecDeque::new(),
+            last_realized: None,
+            crossed_quotes: 0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.mid = None;
+        self.last_timestamp = None;
+        self.quoted.clear();
+        self.effective.clear();
+        self.realized.clear();
+        self.pending.clear();
+        self.last_realized = None;
+        self.crossed_quotes = 0;
+    }
+
+    /// Record a top-of-book quote; returns how many trade markouts it resolved,
+    /// or `None` when the quote is crossed and was skipped.
+    pub fn update_quote(&mut self, bid: f64, ask: f64, timestamp: f64) -> PyResult<Option<usize>> {
+        if !bid.is_finite() || !ask.is_finite() || bid <= 0.0 {
+            return Err(PyValueError::new_err(
+                "quotes must be finite with a positive bid",
+            ));
+        }
+        self.advance_clock(timestamp)?;
+        if ask < bid {
+            self.crossed_quotes += 1;
+            return Ok(None);
+        }
+
+        // Markouts due strictly before this quote see the previous mid.
+        let mut resolved = self.resolve_markouts(|deadline| deadline + MIN_TIME_EPS < timestamp);
+        let mid = 0.5 * (bid + ask);
+        self.mid = Some(mid);
+        resolved += self.resolve_markouts(|deadline| deadline <= timestamp + MIN_TIME_EPS);
+
+        let spread = ask - bid;
+        self.quoted
+            .push(if self.relative { spread / mid } else { spread });
+        Ok(Some(resolved))
+    }
+
+    /// Record a trade with `sign` +1 (buy) / -1 (sell), or 0 to infer it from the
+    /// midpoint. Returns the effective spread, or `None` before the first quote.
+    pub fn update_trade(&mut self, price: f64, sign: f64, timestamp: f64) -> PyResult<Option<f64>> {
+        if !price.is_finite() || price <= 0.0 {
+            return Err(PyValueError::new_err("price must be positive and finite"));
+        }
+        if sign != 1.0 && sign != -1.0 && sign != 0.0 {
+            return Err(PyValueError::new_err("sign must be 1, -1 or 0"));
+        }
+        self.advance_clock(timestamp)?;
+        let Some(mid) = self.mid else {
+            return Ok(None);
+        };
+        let sign = if sign != 0.0 {
+            sign
+        } else if price > mid {
+            1.0
+        } else if price < mid {
+            -1.0
+        } else {
+            0.0
+        };
+
+        let effective = self.scale(2.0 * sign * (price - mid), mid);
+        self.effective.push(effective);
+        self.pending.push_back(PendingMarkout {
+            deadline: timestamp + self.markout_horizon,
+            sign,
+            price,
+        });
+        if self.markout_horizon == 0.0 {
+            self.resolve_markouts(|deadline| deadline <= timestamp + MIN_TIME_EPS);
+        }
+        Ok(Some(effective))
+    }
+
+    pub fn quoted_spread(&self) -> Option<f64> {
+        self.quoted.mean()
+    }
+
+    pub fn effective_spread(&self) -> Option<f64> {
+        self.effective.mean()
+    }
+
+    pub fn realized_spread(&self) -> Option<f64> {
+        self.realized.mean()
+    }
+
+    /// Price impact: effective minus realized spread over the windows.
+    pub fn price_impact(&self) -> Option<f64> {
+        Some(self.effective.mean()? - self.realized.mean()?)
+    }
+
+    pub fn last_realized_spread(&self) -> Option<f64> {
+        self.last_realized
+    }
+
+    pub fn pending_markouts(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of crossed quotes skipped since construction or reset.
+    pub fn crossed_quotes(&self) -> usize {
+        self.crossed_quotes
+    }
+}
+
+impl RustSpreadMetrics {
+    fn advance_clock(&mut self, timestamp: f64) -> PyResult<()> {
+        if !timestamp.is_finite() {
+            return Err(PyValueError::new_err("timestamp must be a finite float"));
+        }
+        if let Some(last_ts) = self.last_timestamp {
+            if timestamp + MIN_TIME_EPS < last_ts {
+                return Err(PyValueError::new_err(
+                    "timestamps must be non-decreasing for spread metrics",
+                ));
+            }
+        }
+        self.last_timestamp = Some(timestamp);
+        Ok(())
+    }
+
+    fn resolve_markouts(&mut self, due: impl Fn(f64) -> bool) -> usize {
+        let Some(mid) = self.mid else {
+            return 0;
+        };
+        let mut resolved = 0;
+        while self.pending.front().is_some_and(|p| due(p.deadline)) {
+            if let Some(p) = self.pending.pop_front() {
+                let realized = self.scale(2.0 * p.sign * (p.price - mid), mid);
+                self.realized.push(realized);
+                self.last_realized = Some(realized);
+                resolved += 1;
+            }
+        }
+        resolved
+    }
+
+    fn scale(&self, spread: f64, mid: f64) -> f64 {
+        if self.relative {
+            spread / mid
+        } else {
+            spread
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+const PRICE_EPS: f64 = 1e-12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Method {
+    LeeReady,
+    Tick,
+}
+
+/// Signs trades with Lee-Ready (quote midpoint, tick-rule fallback) or the pure
+/// tick rule, producing signed volumes for `RustVpinCalculator`.
+#[pyclass]
+pub struct RustTradeClassifier {
+    method: Method,
+    quote_lag: f64,
+    quotes: VecDeque<(f64, f64, f64)>,
+    last_price: Option<f64>,
+    last_tick_sign: f64,
+    last_sign: f64,
+}
+
+#[pymethods]
+impl RustTradeClassifier {
+    #[new]
+    #[pyo3(signature = (method = "lee_ready", quote_lag = 0.0))]
+    pub fn new(method: &str, quote_lag: f64) -> PyResult<Self> {
+        let method = match method {
+            "lee_ready" => Method::LeeReady,
+            "tick" => Method::Tick,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "method must be one of 'lee_ready' or 'tick'",
+                ))
+            }
+        };
+        if !quote_lag.is_finite() || quote_lag < 0.0 {
+            return Err(PyValueError::new_err("quote_lag must be finite and >= 0"));
+        }
+        Ok(Self {
+            method,
+            quote_lag,
+            quotes: VecDeque::new(),
+            last_price: None,
+            last_tick_sign: 0.0,
+            last_sign: 0.0,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.quotes.clear();
+        self.last_price = None;
+        self.last_tick_sign = 0.0;
+        self.last_sign = 0.0;
+    }
+
+    /// Record the prevailing best bid/ask as of `timestamp`; quotes are ignored
+    /// by the tick rule.
+    pub fn update_quote(&mut self, bid: f64, ask: f64, timestamp: f64) -> PyResult<()> {
+        if !bid.is_finite() || !ask.is_finite() || !timestamp.is_finite() {
+            return Err(PyValueError::new_err(
+                "bid, ask and timestamp must be finite floats",
+            ));
+        }
+        if self.method == Method::Tick {
+            return Ok(());
+        }
+        if let Some(&(last_ts, _, _)) = self.quotes.back() {
+            if timestamp < last_ts {
+                return Err(PyValueError::new_err(
+                    "quote timestamps must be non-decreasing",
+                ));
+            }
+        }
+        self.quotes.push_back((timestamp, bid, ask));
+        // Later trades never look further back than this quote's own cutoff.
+        self.prune_quotes(timestamp - self.quote_lag);
+        Ok(())
+    }
+
+    /// Sign one trade; returns `+volume` for buys, `-volume` for sells and `0.0`
+    /// when the trade cannot be classified yet.
+    pub fn classify(&mut self, price: f64, volume: f64, timestamp: f64) -> PyResult<f64> {
+        if !price.is_finite() || !timestamp.is_finite() {
+            return Err(PyValueError::new_err(
+                "price and timestamp must be finite floats",
+            ));
+        }
+        if !volume.is_finite() || volume < 0.0 {
+            return Err(PyValueError::new_err("volume must be finite and >= 0"));
+        }
+        let tick_sign = self.tick_sign(price);
+        let sign = match self.method {
+            Method::LeeReady => {
+                let quote_sign = self.quote_sign(price, timestamp);
+                if quote_sign != 0.0 {
+                    quote_sign
+                } else {
+                    tick_sign
+                }
+            }
+            Method::Tick => tick_sign,
+        };
+        self.last_sign = sign;
+        Ok(sign * volume)
+    }
+
+    pub fn classify_many<'py>(
+        &mut self,
+        prices: PyReadonlyArray1<'py, f64>,
+        volumes: PyReadonlyArray1<'py, f64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<f64>> {
+        let prices = prices.as_slice()?;
+        let volumes = volumes.as_slice()?;
+        let timestamps = timestamps.as_slice()?;
+        if prices.len() != volumes.len() || prices.len() != timestamps.len() {
+            return Err(PyValueError::new_err(
+                "prices/volumes/timestamps arrays must have matching length",
+            ));
+        }
+        let mut out = Vec::with_capacity(prices.len());
+        for ((&price, &volume), &ts) in prices.iter().zip(volumes).zip(timestamps) {
+            out.push(self.classify(price, volume, ts)?);
+        }
+        Ok(out)
+    }
+
+    /// `classify_many` returning a float64 array instead of a list.
+    pub fn classify_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        prices: PyReadonlyArray1<'py, f64>,
+        volumes: PyReadonlyArray1<'py, f64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        Ok(self
+            .classify_many(prices, volumes, timestamps)?
+            .into_pyarray(py))
+    }
+
+    /// Sign of the most recent trade: `1.0`, `-1.0` or `0.0`.
+    pub fn last_sign(&self) -> f64 {
+        self.last_sign
+    }
+
+    /// Number of quotes kept for the Lee-Ready lookup.
+    pub fn buffered_quotes(&self) -> usize {
+        self.quotes.len()
+    }
+}
+
+impl RustTradeClassifier {
+    /// Tick rule: compare with the last trade at a different price, carrying the
+    /// previous sign through zero ticks.
+    fn tick_sign(&mut self, price: f64) -> f64 {
+        if let Some(last) = self.last_price {
+            if price > last + PRICE_EPS {
+                self.last_tick_sign = 1.0;
+            } else if price + PRICE_EPS < last {
+                self.last_tick_sign = -1.0;
+            }
+        }
+        self.last_price = Some(price);
+        self.last_tick_sign
+    }
+
+    /// Drop quotes superseded by a newer one at or before `cutoff`.
+    fn prune_quotes(&mut self, cutoff: f64) {
+        while self.quotes.len() > 1 && self.quotes[1].0 <= cutoff {
+            self.quotes.pop_front();
+        }
+    }
+
+    /// Quote rule against the midpoint prevailing `quote_lag` before the trade.
+    fn quote_sign(&mut self, price: f64, timestamp: f64) -> f64 {
+        let cutoff = timestamp - self.quote_lag;
+        self.prune_quotes(cutoff);
+        match self.quotes.front() {
+            Some(&(quote_ts, bid, ask)) if quote_ts <= cutoff => {
+                let mid = 0.5 * (bid + ask);
+                if price > mid + PRICE_EPS {
+                    1.0
+                } else if price + PRICE_EPS < mid {
+                    -1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+}
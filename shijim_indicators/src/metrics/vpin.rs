@@ -1,9 +1,13 @@
-use numpy::PyReadonlyArray1;
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::collections::VecDeque;
 
 const BUCKET_EPS: f64 = 1e-9;
+/// Upper bound applied to `window_size` unless the caller overrides it.
+pub const DEFAULT_MAX_WINDOW_SIZE: usize = 1_000_000;
+/// Buckets reserved up front; larger windows grow the deque on demand.
+const INITIAL_WINDOW_CAPACITY: usize = 1024;
 
 #[pyclass]
 pub struct RustVpinCalculator {
@@ -14,13 +18,24 @@ pub struct RustVpinCalculator {
     sell_volume: f64,
     imbalances: VecDeque<f64>,
     imbalance_sum: f64,
+    bulk_classification: bool,
 }
 
 #[pymethods]
 impl RustVpinCalculator {
     #[new]
-    #[pyo3(text_signature = "(bucket_volume, window_size)")]
-    pub fn new(bucket_volume: f64, window_size: usize) -> PyResult<Self> {
+    #[pyo3(signature = (
+        bucket_volume,
+        window_size,
+        max_window_size = DEFAULT_MAX_WINDOW_SIZE,
+        bulk_classification = false
+    ))]
+    pub fn new(
+        bucket_volume: f64,
+        window_size: usize,
+        max_window_size: usize,
+        bulk_classification: bool,
+    ) -> PyResult<Self> {
         if !bucket_volume.is_finite() || bucket_volume <= 0.0 {
             return Err(PyValueError::new_err(
                 "bucket_volume must be a positive, finite number",
@@ -29,14 +44,20 @@ impl RustVpinCalculator {
         if window_size == 0 {
             return Err(PyValueError::new_err("window_size must be >= 1"));
         }
+        if window_size > max_window_size {
+            return Err(PyValueError::new_err(format!(
+                "window_size must be <= {max_window_size} (got {window_size})"
+            )));
+        }
         Ok(Self {
             bucket_volume,
             window_size,
             filled_volume: 0.0,
             buy_volume: 0.0,
             sell_volume: 0.0,
-            imbalances: VecDeque::with_capacity(window_size),
+            imbalances: VecDeque::with_capacity(window_size.min(INITIAL_WINDOW_CAPACITY)),
             imbalance_sum: 0.0,
+            bulk_classification,
         })
     }
 
@@ -49,20 +70,91 @@ impl RustVpinCalculator {
     }
 
     pub fn update_signed_volume(&mut self, signed_volume: f64) -> PyResult<Option<f64>> {
+        if self.bulk_classification {
+            return Err(PyValueError::new_err(
+                "calculator uses bulk volume classification; call update_bulk instead",
+            ));
+        }
         self.consume_trade(signed_volume)?;
         Ok(self.current_vpin())
     }
 
+    /// Bulk-volume classification (Easley, Lopez de Prado, O'Hara): the buy share of
+    /// `volume` is `Phi(price_change / sigma)`, the rest is attributed to sellers.
+    pub fn update_bulk(
+        &mut self,
+        volume: f64,
+        price_change: f64,
+        sigma: f64,
+    ) -> PyResult<Option<f64>> {
+        if !self.bulk_classification {
+            return Err(PyValueError::new_err(
+                "bulk volume classification is disabled; construct with bulk_classification=True",
+            ));
+        }
+        if !volume.is_finite() || volume < 0.0 {
+            return Err(PyValueError::new_err("volume must be finite and >= 0"));
+        }
+        if !price_change.is_finite() {
+            return Err(PyValueError::new_err("price_change must be a finite float"));
+        }
+        if !sigma.is_finite() || sigma <= 0.0 {
+            return Err(PyValueError::new_err(
+                "sigma must be a positive, finite number",
+            ));
+        }
+        let buy_share = normal_cdf(price_change / sigma);
+        self.consume_split(volume, buy_share);
+        Ok(self.current_vpin())
+    }
+
+    pub fn update_bulk_series<'py>(
+        &mut self,
+        volumes: PyReadonlyArray1<'py, f64>,
+        price_changes: PyReadonlyArray1<'py, f64>,
+        sigmas: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        self.bulk_series(
+            volumes.as_slice()?,
+            price_changes.as_slice()?,
+            sigmas.as_slice()?,
+            |vpin| vpin,
+        )
+    }
+
+    /// `update_bulk_series` returning a float64 array with NaN before VPIN is ready.
+    pub fn update_bulk_series_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        volumes: PyReadonlyArray1<'py, f64>,
+        price_changes: PyReadonlyArray1<'py, f64>,
+        sigmas: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out = self.bulk_series(
+            volumes.as_slice()?,
+            price_changes.as_slice()?,
+            sigmas.as_slice()?,
+            |vpin| vpin.unwrap_or(f64::NAN),
+        )?;
+        Ok(out.into_pyarray(py))
+    }
+
     pub fn update_signed_series<'py>(
         &mut self,
         signed_volumes: PyReadonlyArray1<'py, f64>,
     ) -> PyResult<Vec<Option<f64>>> {
-        let slice = signed_volumes.as_slice()?;
-        let mut out = Vec::with_capacity(slice.len());
-        for &value in slice {
-            out.push(self.update_signed_volume(value)?);
-        }
-        Ok(out)
+        self.signed_series(signed_volumes.as_slice()?, |vpin| vpin)
+    }
+
+    /// `update_signed_series` returning a float64 array with NaN before VPIN is ready.
+    pub fn update_signed_series_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        signed_volumes: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out =
+            self.signed_series(signed_volumes.as_slice()?, |vpin| vpin.unwrap_or(f64::NAN))?;
+        Ok(out.into_pyarray(py))
     }
 
     pub fn buckets_ready(&self) -> usize {
@@ -72,9 +164,44 @@ impl RustVpinCalculator {
     pub fn bucket_volume(&self) -> f64 {
         self.bucket_volume
     }
+
+    pub fn uses_bulk_classification(&self) -> bool {
+        self.bulk_classification
+    }
 }
 
 impl RustVpinCalculator {
+    fn bulk_series<T>(
+        &mut self,
+        volumes: &[f64],
+        price_changes: &[f64],
+        sigmas: &[f64],
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        if volumes.len() != price_changes.len() || volumes.len() != sigmas.len() {
+            return Err(PyValueError::new_err(
+                "volumes/price_changes/sigmas arrays must have matching length",
+            ));
+        }
+        let mut out = Vec::with_capacity(volumes.len());
+        for ((&volume, &change), &sigma) in volumes.iter().zip(price_changes).zip(sigmas) {
+            out.push(emit(self.update_bulk(volume, change, sigma)?));
+        }
+        Ok(out)
+    }
+
+    fn signed_series<T>(
+        &mut self,
+        signed_volumes: &[f64],
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        let mut out = Vec::with_capacity(signed_volumes.len());
+        for &value in signed_volumes {
+            out.push(emit(self.update_signed_volume(value)?));
+        }
+        Ok(out)
+    }
+
     fn consume_trade(&mut self, signed_volume: f64) -> PyResult<()> {
         if !signed_volume.is_finite() {
             return Err(PyValueError::new_err(
@@ -85,8 +212,14 @@ impl RustVpinCalculator {
             return Ok(());
         }
 
-        let direction_is_buy = signed_volume > 0.0;
-        let mut remaining = signed_volume.abs();
+        let buy_share = if signed_volume > 0.0 { 1.0 } else { 0.0 };
+        self.consume_split(signed_volume.abs(), buy_share);
+        Ok(())
+    }
+
+    /// Pour `volume` into buckets, attributing `buy_share` of it to buyers.
+    fn consume_split(&mut self, volume: f64, buy_share: f64) {
+        let mut remaining = volume;
 
         while remaining > 0.0 {
             if self.bucket_is_full() {
@@ -102,11 +235,8 @@ impl RustVpinCalculator {
                 continue;
             }
 
-            if direction_is_buy {
-                self.buy_volume += take;
-            } else {
-                self.sell_volume += take;
-            }
+            self.buy_volume += take * buy_share;
+            self.sell_volume += take * (1.0 - buy_share);
             self.filled_volume += take;
             remaining -= take;
 
@@ -114,8 +244,6 @@ impl RustVpinCalculator {
                 self.finalize_bucket();
             }
         }
-
-        Ok(())
     }
 
     fn bucket_is_full(&self) -> bool {
@@ -147,3 +275,25 @@ impl RustVpinCalculator {
         Some(self.imbalance_sum / denom)
     }
 }
+
+/// Standard normal CDF via the Abramowitz-Stegun 7.1.26 erf approximation
+/// (absolute error < 1.5e-7).
+fn normal_cdf(x: f64) -> f64 {
+    const P: f64 = 0.327_591_1;
+    const A: [f64; 5] = [
+        0.254_829_592,
+        -0.284_496_736,
+        1.421_413_741,
+        -1.453_152_027,
+        1.061_405_429,
+    ];
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + P * z);
+    let poly = t * (A[0] + t * (A[1] + t * (A[2] + t * (A[3] + t * A[4]))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
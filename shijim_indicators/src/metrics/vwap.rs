@@ -0,0 +1,305 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::VecDeque;
+
+use super::Resettable;
+
+const MIN_TIME_EPS: f64 = 1e-12;
+
+/// Session and rolling VWAP/TWAP.
+///
+/// The session accumulators run from the last `reset_session` (or construction)
+/// and, when an anchor timestamp is given, ignore trades before it. The rolling
+/// figures cover the trailing `window_duration` ending at the latest trade.
+/// TWAP treats the last traded price as prevailing until the next trade.
+#[pyclass]
+pub struct RustVwapCalculator {
+    window_duration: f64,
+    // Anchor given at construction; `reset` and session rolls restore it.
+    configured_anchor_ts: Option<f64>,
+    anchor_ts: Option<f64>,
+    last_timestamp: Option<f64>,
+    last_price: Option<f64>,
+    session_start: Option<f64>,
+    session_notional: f64,
+    session_volume: f64,
+    session_area: f64,
+    trades: VecDeque<(f64, f64, f64)>,
+    window_notional: f64,
+    window_volume: f64,
+    // Price prevailing at the start of the rolling window.
+    carry_price: Option<f64>,
+}
+
+#[pymethods]
+impl RustVwapCalculator {
+    #[new]
+    #[pyo3(signature = (window_duration, anchor_ts = None))]
+    pub fn new(window_duration: f64, anchor_ts: Option<f64>) -> PyResult<Self> {
+        if !window_duration.is_finite() || window_duration <= 0.0 {
+            return Err(PyValueError::new_err(
+                "window_duration must be a positive, finite number",
+            ));
+        }
+        if anchor_ts.is_some_and(|ts| !ts.is_finite()) {
+            return Err(PyValueError::new_err("anchor_ts must be a finite float"));
+        }
+        Ok(Self {
+            window_duration,
+            configured_anchor_ts: anchor_ts,
+            anchor_ts,
+            last_timestamp: None,
+            last_price: None,
+            session_start: None,
+            session_notional: 0.0,
+            session_volume: 0.0,
+            session_area: 0.0,
+            trades: VecDeque::new(),
+            window_notional: 0.0,
+            window_volume: 0.0,
+            carry_price: None,
+        })
+    }
+
+    /// Clear all state, including the rolling window; the constructor's anchor
+    /// is restored.
+    pub fn reset(&mut self) {
+        self.anchor_ts = self.configured_anchor_ts;
+        self.last_timestamp = None;
+        self.last_price = None;
+        self.clear_session();
+        self.trades.clear();
+        self.window_notional = 0.0;
+        self.window_volume = 0.0;
+        self.carry_price = None;
+    }
+
+    /// Start a new session, optionally anchored at `anchor_ts`; the rolling
+    /// window is kept.
+    #[pyo3(signature = (anchor_ts = None))]
+    pub fn reset_session(&mut self, anchor_ts: Option<f64>) -> PyResult<()> {
+        if let Some(anchor) = anchor_ts {
+            if !anchor.is_finite() {
+                return Err(PyValueError::new_err("anchor_ts must be a finite float"));
+            }
+            if self
+                .last_timestamp
+                .is_some_and(|last_ts| anchor + MIN_TIME_EPS < last_ts)
+            {
+                return Err(PyValueError::new_err(
+                    "anchor_ts must not precede the last processed trade",
+                ));
+            }
+        }
+        self.anchor_ts = anchor_ts;
+        self.clear_session();
+        Ok(())
+    }
+
+    /// Feed one trade; returns the session VWAP, or `None` while the session has
+    /// no volume (e.g. before the anchor).
+    pub fn update(&mut self, price: f64, volume: f64, timestamp: f64) -> PyResult<Option<f64>> {
+        if !price.is_finite() || price <= 0.0 {
+            return Err(PyValueError::new_err("price must be positive and finite"));
+        }
+        if !volume.is_finite() || volume < 0.0 {
+            return Err(PyValueError::new_err("volume must be finite and >= 0"));
+        }
+        if !timestamp.is_finite() {
+            return Err(PyValueError::new_err("timestamp must be a finite float"));
+        }
+        if let Some(last_ts) = self.last_timestamp {
+            if timestamp + MIN_TIME_EPS < last_ts {
+                return Err(PyValueError::new_err(
+                    "timestamps must be non-decreasing for VWAP updates",
+                ));
+            }
+        }
+
+        let in_session = self
+            .anchor_ts
+            .is_none_or(|anchor| timestamp + MIN_TIME_EPS >= anchor);
+        if in_session {
+            self.push_session(price, volume, timestamp);
+        }
+        self.push_window(price, volume, timestamp);
+        self.last_timestamp = Some(timestamp);
+        self.last_price = Some(price);
+        Ok(self.session_vwap())
+    }
+
+    pub fn update_many<'py>(
+        &mut self,
+        prices: PyReadonlyArray1<'py, f64>,
+        volumes: PyReadonlyArray1<'py, f64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<Vec<Option<f64>>> {
+        self.series(
+            prices.as_slice()?,
+            volumes.as_slice()?,
+            timestamps.as_slice()?,
+            |vwap| vwap,
+        )
+    }
+
+    /// `update_many` returning a float64 array with NaN while the session is empty.
+    pub fn update_many_np<'py>(
+        &mut self,
+        py: Python<'py>,
+        prices: PyReadonlyArray1<'py, f64>,
+        volumes: PyReadonlyArray1<'py, f64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let out = self.series(
+            prices.as_slice()?,
+            volumes.as_slice()?,
+            timestamps.as_slice()?,
+            |vwap| vwap.unwrap_or(f64::NAN),
+        )?;
+        Ok(out.into_pyarray(py))
+    }
+
+    pub fn session_vwap(&self) -> Option<f64> {
+        (self.session_volume > 0.0).then(|| self.session_notional / self.session_volume)
+    }
+
+    /// Session TWAP up to the latest trade.
+    pub fn session_twap(&self) -> Option<f64> {
+        let start = self.session_start?;
+        let now = self.last_timestamp?;
+        let elapsed = now - start;
+        if elapsed <= MIN_TIME_EPS {
+            return self.last_price;
+        }
+        Some(self.session_area / elapsed)
+    }
+
+    pub fn rolling_vwap(&self) -> Option<f64> {
+        (self.window_volume > 0.0).then(|| self.window_notional / self.window_volume)
+    }
+
+    /// TWAP over the trailing window, using the price prevailing at its start.
+    pub fn rolling_twap(&self) -> Option<f64> {
+        let now = self.last_timestamp?;
+        let window_start = now - self.window_duration;
+        let (mut prev_ts, mut prev_price) = match self.carry_price {
+            Some(price) => (window_start, price),
+            None => {
+                let &(ts, price, _) = self.trades.front()?;
+                (ts, price)
+            }
+        };
+        let start = prev_ts;
+        let mut area = 0.0;
+        for &(ts, price, _) in &self.trades {
+            area += prev_price * (ts - prev_ts);
+            prev_ts = ts;
+            prev_price = price;
+        }
+        let elapsed = now - start;
+        if elapsed <= MIN_TIME_EPS {
+            return self.last_price;
+        }
+        Some(area / elapsed)
+    }
+
+    pub fn session_volume(&self) -> f64 {
+        self.session_volume
+    }
+
+    pub fn anchor_ts(&self) -> Option<f64> {
+        self.anchor_ts
+    }
+
+    pub fn window_duration(&self) -> f64 {
+        self.window_duration
+    }
+}
+
+impl RustVwapCalculator {
+    fn series<T>(
+        &mut self,
+        prices: &[f64],
+        volumes: &[f64],
+        timestamps: &[f64],
+        emit: impl Fn(Option<f64>) -> T,
+    ) -> PyResult<Vec<T>> {
+        if prices.len() != volumes.len() || prices.len() != timestamps.len() {
+            return Err(PyValueError::new_err(
+                "prices/volumes/timestamps arrays must have matching length",
+            ));
+        }
+        let mut out = Vec::with_capacity(prices.len());
+        for ((&price, &volume), &ts) in prices.iter().zip(volumes).zip(timestamps) {
+            out.push(emit(self.update(price, volume, ts)?));
+        }
+        Ok(out)
+    }
+
+    fn clear_session(&mut self) {
+        self.session_start = None;
+        self.session_notional = 0.0;
+        self.session_volume = 0.0;
+        self.session_area = 0.0;
+    }
+
+    fn push_session(&mut self, price: f64, volume: f64, timestamp: f64) {
+        match self.session_start {
+            Some(_) => {
+                if let (Some(last_ts), Some(last_price)) = (self.last_timestamp, self.last_price) {
+                    self.session_area += last_price * (timestamp - last_ts);
+                }
+            }
+            // An anchored session starts at the anchor with the price prevailing
+            // there, if one was seen; an anchor already passed (e.g. after a
+            // session roll) starts it at the first trade instead.
+            None => match (self.anchor_ts, self.last_price) {
+                (Some(anchor), Some(last_price))
+                    if self.last_timestamp.is_none_or(|ts| ts <= anchor) =>
+                {
+                    self.session_start = Some(anchor);
+                    self.session_area += last_price * (timestamp - anchor).max(0.0);
+                }
+                _ => self.session_start = Some(timestamp),
+            },
+        }
+        self.session_notional += price * volume;
+        self.session_volume += volume;
+    }
+
+    fn push_window(&mut self, price: f64, volume: f64, timestamp: f64) {
+        self.trades.push_back((timestamp, price, volume));
+        self.window_notional += price * volume;
+        self.window_volume += volume;
+        let window_start = timestamp - self.window_duration;
+        while self
+            .trades
+            .front()
+            .is_some_and(|&(ts, _, _)| ts + MIN_TIME_EPS < window_start)
+        {
+            if let Some((_, old_price, old_volume)) = self.trades.pop_front() {
+                self.window_notional -= old_price * old_volume;
+                self.window_volume -= old_volume;
+                self.carry_price = Some(old_price);
+            }
+        }
+        if self.trades.len() == 1 {
+            // Avoid drift once the window is down to the latest trade.
+            self.window_notional = price * volume;
+            self.window_volume = volume;
+        }
+    }
+}
+
+impl Resettable for RustVwapCalculator {
+    fn reset(&mut self) {
+        RustVwapCalculator::reset(self);
+    }
+
+    /// Start a session under the configured anchor but keep the rolling window.
+    fn roll_session(&mut self) {
+        self.anchor_ts = self.configured_anchor_ts;
+        self.clear_session();
+    }
+}
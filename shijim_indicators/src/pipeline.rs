@@ -0,0 +1,558 @@
+use numpy::{PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::metrics::hawkes::RustHawkesIntensity;
+use crate::metrics::ofi::{OfiUpdate, RustOfiCalculator};
+use crate::metrics::rolling::RustRollingStats;
+use crate::metrics::trade_sign::RustTradeClassifier;
+use crate::metrics::vpin::{RustVpinCalculator, DEFAULT_MAX_WINDOW_SIZE};
+use crate::metrics::Resettable;
+
+/// Message type codes accepted by `RustPipeline.process`. `SYSTEM_CODE` carries
+/// decoded trading-status messages such as the template-1100 system event.
+const TRADE_CODE: i64 = 0;
+const QUOTE_CODE: i64 = 1;
+const SYSTEM_CODE: i64 = 2;
+
+// Normalized event codes carried by system messages. The pipeline does not
+// read venue codes directly: the template-1100 schema is not part of this
+// crate, so its decoder translates the event type onto these codes:
+//
+//   pre-open / opening auction call     -> EVENT_PRE_OPEN
+//   start of continuous trading         -> EVENT_OPEN
+//   trading halt or suspension          -> EVENT_HALT
+//   end of trading                      -> EVENT_CLOSE
+//   trading resumed after a halt        -> EVENT_RESUME
+//
+// Only a start of trading should map to EVENT_OPEN, since that code rolls the
+// session by default.
+pub const EVENT_PRE_OPEN: i64 = 1;
+pub const EVENT_OPEN: i64 = 2;
+pub const EVENT_HALT: i64 = 3;
+pub const EVENT_CLOSE: i64 = 4;
+/// Trading resumed after an intraday halt; not a session boundary by default.
+pub const EVENT_RESUME: i64 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageKind {
+    Trade,
+    Quote,
+    System,
+}
+
+impl MessageKind {
+    fn parse(kind: &str) -> PyResult<Self> {
+        match kind {
+            "trade" => Ok(Self::Trade),
+            "quote" => Ok(Self::Quote),
+            _ => Err(PyValueError::new_err(
+                "message type must be one of 'trade' or 'quote'",
+            )),
+        }
+    }
+}
+
+/// A decoded market data message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Message {
+    /// `sign` is +1 (buy) / -1 (sell), or 0 to classify with Lee-Ready.
+    Trade {
+        timestamp: f64,
+        price: f64,
+        volume: f64,
+        sign: f64,
+    },
+    Quote {
+        timestamp: f64,
+        bid_price: f64,
+        bid_size: f64,
+        ask_price: f64,
+        ask_size: f64,
+    },
+    /// Trading-status change such as a session open or close.
+    System { timestamp: f64, event_code: i64 },
+}
+
+impl Message {
+    pub fn kind(&self) -> MessageKind {
+        match self {
+            Message::Trade { .. } => MessageKind::Trade,
+            Message::Quote { .. } => MessageKind::Quote,
+            Message::System { .. } => MessageKind::System,
+        }
+    }
+
+    pub fn timestamp(&self) -> f64 {
+        match *self {
+            Message::Trade { timestamp, .. }
+            | Message::Quote { timestamp, .. }
+            | Message::System { timestamp, .. } => timestamp,
+        }
+    }
+}
+
+/// Message field fed into a rolling-stats node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Price,
+    Volume,
+    Mid,
+    Spread,
+}
+
+impl Field {
+    fn parse(field: &str) -> PyResult<Self> {
+        match field {
+            "price" => Ok(Self::Price),
+            "volume" => Ok(Self::Volume),
+            "mid" => Ok(Self::Mid),
+            "spread" => Ok(Self::Spread),
+            _ => Err(PyValueError::new_err(
+                "field must be one of 'price', 'volume', 'mid' or 'spread'",
+            )),
+        }
+    }
+
+    fn extract(&self, message: &Message) -> Option<f64> {
+        match (self, message) {
+            (Field::Price, Message::Trade { price, .. }) => Some(*price),
+            (Field::Volume, Message::Trade { volume, .. }) => Some(*volume),
+            (
+                Field::Mid,
+                Message::Quote {
+                    bid_price,
+                    ask_price,
+                    ..
+                },
+            ) => Some(0.5 * (bid_price + ask_price)),
+            (
+                Field::Spread,
+                Message::Quote {
+                    bid_price,
+                    ask_price,
+                    ..
+                },
+            ) => Some(ask_price - bid_price),
+            _ => None,
+        }
+    }
+}
+
+enum Indicator {
+    Vpin(RustVpinCalculator),
+    Ofi(RustOfiCalculator),
+    Hawkes(RustHawkesIntensity, MessageKind),
+    Rolling(RustRollingStats, Field),
+}
+
+struct Node {
+    name: String,
+    indicator: Indicator,
+    value: Option<f64>,
+}
+
+impl Node {
+    /// Feed a message; `signed_volume` is the classified trade volume.
+    fn consume(&mut self, message: &Message, signed_volume: f64) -> PyResult<()> {
+        match (&mut self.indicator, message) {
+            (Indicator::Vpin(vpin), Message::Trade { .. }) => {
+                self.value = vpin.update_signed_volume(signed_volume)?;
+            }
+            (
+                Indicator::Ofi(ofi),
+                Message::Quote {
+                    bid_price,
+                    bid_size,
+                    ask_price,
+                    ask_size,
+                    ..
+                },
+            ) => {
+                self.value =
+                    match ofi.step(&[*bid_price], &[*bid_size], &[*ask_price], &[*ask_size])? {
+                        OfiUpdate::WarmingUp => None,
+                        OfiUpdate::MissingDepth => Some(0.0),
+                        OfiUpdate::Value(value) => Some(value),
+                    };
+            }
+            (Indicator::Hawkes(hawkes, kind), _) if *kind == message.kind() => {
+                self.value = Some(hawkes.update(message.timestamp())?);
+            }
+            (Indicator::Rolling(stats, field), _) => {
+                if let Some(input) = field.extract(message) {
+                    self.value = stats.update(input)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn indicator_mut(&mut self) -> &mut dyn Resettable {
+        match &mut self.indicator {
+            Indicator::Vpin(vpin) => vpin,
+            Indicator::Ofi(ofi) => ofi,
+            Indicator::Hawkes(hawkes, _) => hawkes,
+            Indicator::Rolling(stats, _) => stats,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.indicator_mut().reset();
+        self.value = None;
+    }
+
+    fn roll_session(&mut self) {
+        self.indicator_mut().roll_session();
+        self.value = None;
+    }
+}
+
+/// Runs several indicators over one stream of decoded trades and quotes.
+///
+/// Each registered indicator is wired to the message type it consumes; a
+/// message updates every matching indicator in a single Rust pass, and
+/// `latest()` returns the most recent value of each by name. Trades without a
+/// sign are classified with Lee-Ready against the quotes seen so far.
+///
+/// Messages must arrive in non-decreasing timestamp order; a rejected message
+/// leaves every indicator and the classifier untouched.
+///
+/// System messages whose event code is in `session_events` (default: open)
+/// roll every indicator into a new session.
+#[pyclass]
+pub struct RustPipeline {
+    nodes: Vec<Node>,
+    classifier: RustTradeClassifier,
+    session_events: Vec<i64>,
+    sessions_rolled: u64,
+    last_timestamp: Option<f64>,
+}
+
+#[pymethods]
+impl RustPipeline {
+    #[classattr]
+    const TRADE: i64 = TRADE_CODE;
+    #[classattr]
+    const QUOTE: i64 = QUOTE_CODE;
+    #[classattr]
+    const SYSTEM: i64 = SYSTEM_CODE;
+    #[classattr]
+    const EVENT_PRE_OPEN: i64 = EVENT_PRE_OPEN;
+    #[classattr]
+    const EVENT_OPEN: i64 = EVENT_OPEN;
+    #[classattr]
+    const EVENT_HALT: i64 = EVENT_HALT;
+    #[classattr]
+    const EVENT_CLOSE: i64 = EVENT_CLOSE;
+    #[classattr]
+    const EVENT_RESUME: i64 = EVENT_RESUME;
+
+    #[new]
+    #[pyo3(signature = (session_events = None))]
+    pub fn new(session_events: Option<Vec<i64>>) -> PyResult<Self> {
+        Ok(Self {
+            nodes: Vec::new(),
+            classifier: RustTradeClassifier::new("lee_ready", 0.0)?,
+            session_events: session_events.unwrap_or_else(|| vec![EVENT_OPEN]),
+            sessions_rolled: 0,
+            last_timestamp: None,
+        })
+    }
+
+    /// Reset every indicator and the trade classifier; registrations are kept.
+    pub fn reset(&mut self) {
+        self.nodes.iter_mut().for_each(Node::reset);
+        self.classifier.reset();
+        self.last_timestamp = None;
+    }
+
+    /// VPIN over signed trade volume.
+    pub fn add_vpin(&mut self, name: &str, bucket_volume: f64, window_size: usize) -> PyResult<()> {
+        let vpin =
+            RustVpinCalculator::new(bucket_volume, window_size, DEFAULT_MAX_WINDOW_SIZE, false)?;
+        self.register(name, Indicator::Vpin(vpin))
+    }
+
+    /// Top-of-book OFI over quotes.
+    pub fn add_ofi(&mut self, name: &str) -> PyResult<()> {
+        self.register(name, Indicator::Ofi(RustOfiCalculator::new()))
+    }
+
+    /// Hawkes intensity with one event per message of type `on`.
+    #[pyo3(signature = (name, baseline, alpha, beta, on = "trade"))]
+    pub fn add_hawkes(
+        &mut self,
+        name: &str,
+        baseline: f64,
+        alpha: f64,
+        beta: f64,
+        on: &str,
+    ) -> PyResult<()> {
+        let kind = MessageKind::parse(on)?;
+        let hawkes = RustHawkesIntensity::new(baseline, alpha, beta, false)?;
+        self.register(name, Indicator::Hawkes(hawkes, kind))
+    }
+
+    /// Rolling z-score of a message field: trade `price`/`volume` or quote
+    /// `mid`/`spread`.
+    #[pyo3(signature = (name, window_size, field = "price", half_life = None))]
+    pub fn add_rolling(
+        &mut self,
+        name: &str,
+        window_size: usize,
+        field: &str,
+        half_life: Option<f64>,
+    ) -> PyResult<()> {
+        let field = Field::parse(field)?;
+        let stats = RustRollingStats::new(window_size, half_life)?;
+        self.register(name, Indicator::Rolling(stats, field))
+    }
+
+    #[pyo3(signature = (price, volume, timestamp, sign = 0.0))]
+    pub fn on_trade(&mut self, price: f64, volume: f64, timestamp: f64, sign: f64) -> PyResult<()> {
+        self.consume(&Message::Trade {
+            timestamp,
+            price,
+            volume,
+            sign,
+        })
+    }
+
+    pub fn on_quote(
+        &mut self,
+        bid_price: f64,
+        bid_size: f64,
+        ask_price: f64,
+        ask_size: f64,
+        timestamp: f64,
+    ) -> PyResult<()> {
+        self.consume(&Message::Quote {
+            timestamp,
+            bid_price,
+            bid_size,
+            ask_price,
+            ask_size,
+        })
+    }
+
+    /// Apply a system event; returns whether it rolled the indicators into a
+    /// new session.
+    pub fn on_system_event(&mut self, event_code: i64, timestamp: f64) -> PyResult<bool> {
+        let rolled = self.sessions_rolled;
+        self.consume(&Message::System {
+            timestamp,
+            event_code,
+        })?;
+        Ok(self.sessions_rolled > rolled)
+    }
+
+    /// Consume a batch of messages and return `latest()`.
+    ///
+    /// `kinds` holds `RustPipeline.TRADE` / `QUOTE` / `SYSTEM` codes and
+    /// `fields` is `(n, 4)`: `[price, volume, sign, _]` for trades,
+    /// `[bid_price, bid_size, ask_price, ask_size]` for quotes and
+    /// `[event_code, _, _, _]` for system events.
+    pub fn process<'py>(
+        &mut self,
+        py: Python<'py>,
+        kinds: PyReadonlyArray1<'py, i64>,
+        timestamps: PyReadonlyArray1<'py, f64>,
+        fields: PyReadonlyArray2<'py, f64>,
+    ) -> PyResult<&'py PyDict> {
+        let kinds = kinds.as_slice()?;
+        let timestamps = timestamps.as_slice()?;
+        let shape = fields.shape();
+        if kinds.len() != timestamps.len() || shape[0] != kinds.len() || shape[1] != 4 {
+            return Err(PyValueError::new_err(
+                "kinds/timestamps must match and fields must be shaped (n, 4)",
+            ));
+        }
+        let fields = fields.as_slice()?;
+        for ((&kind, &timestamp), row) in kinds.iter().zip(timestamps).zip(fields.chunks(4)) {
+            let message = match kind {
+                TRADE_CODE => Message::Trade {
+                    timestamp,
+                    price: row[0],
+                    volume: row[1],
+                    sign: row[2],
+                },
+                QUOTE_CODE => Message::Quote {
+                    timestamp,
+                    bid_price: row[0],
+                    bid_size: row[1],
+                    ask_price: row[2],
+                    ask_size: row[3],
+                },
+                SYSTEM_CODE => Message::System {
+                    timestamp,
+                    event_code: Self::event_code(row[0])?,
+                },
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "unknown message kind {kind}"
+                    )))
+                }
+            };
+            self.consume(&message)?;
+        }
+        self.latest(py)
+    }
+
+    /// Latest value per indicator name (`None` until the indicator is ready).
+    pub fn latest<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+        for node in &self.nodes {
+            dict.set_item(&node.name, node.value)?;
+        }
+        Ok(dict)
+    }
+
+    /// Indicator names in registration order.
+    pub fn names(&self) -> Vec<String> {
+        self.nodes.iter().map(|node| node.name.clone()).collect()
+    }
+
+    pub fn session_events(&self) -> Vec<i64> {
+        self.session_events.clone()
+    }
+
+    /// Number of session boundaries applied since construction.
+    pub fn sessions_rolled(&self) -> u64 {
+        self.sessions_rolled
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+impl RustPipeline {
+    /// Route one decoded message to every indicator wired to its type.
+    pub fn consume(&mut self, message: &Message) -> PyResult<()> {
+        // Reject bad input before touching any state, so an error never leaves
+        // the classifier or some of the nodes updated.
+        self.validate(message)?;
+        let mut signed_volume = 0.0;
+        match *message {
+            Message::Trade {
+                timestamp,
+                price,
+                volume,
+                sign,
+            } => {
+                // Always classify so the tick-rule state sees every trade.
+                let classified = self.classifier.classify(price, volume, timestamp)?;
+                signed_volume = if sign != 0.0 {
+                    sign * volume
+                } else {
+                    classified
+                };
+            }
+            Message::Quote {
+                timestamp,
+                bid_price,
+                ask_price,
+                ..
+            } => self
+                .classifier
+                .update_quote(bid_price, ask_price, timestamp)?,
+            Message::System { event_code, .. } => {
+                if self.session_events.contains(&event_code) {
+                    self.nodes.iter_mut().for_each(Node::roll_session);
+                    self.classifier.roll_session();
+                    self.sessions_rolled += 1;
+                }
+            }
+        }
+        if message.kind() != MessageKind::System {
+            for node in &mut self.nodes {
+                node.consume(message, signed_volume)?;
+            }
+        }
+        self.last_timestamp = Some(message.timestamp());
+        Ok(())
+    }
+
+    /// Everything the classifier and the nodes would reject: non-finite or
+    /// negative inputs and timestamps that go back in time.
+    fn validate(&self, message: &Message) -> PyResult<()> {
+        let timestamp = message.timestamp();
+        if !timestamp.is_finite() {
+            return Err(PyValueError::new_err("timestamp must be a finite float"));
+        }
+        if self
+            .last_timestamp
+            .is_some_and(|last_ts| timestamp < last_ts)
+        {
+            return Err(PyValueError::new_err(
+                "message timestamps must be non-decreasing",
+            ));
+        }
+        match *message {
+            Message::Trade {
+                price,
+                volume,
+                sign,
+                ..
+            } => {
+                if sign != 1.0 && sign != -1.0 && sign != 0.0 {
+                    return Err(PyValueError::new_err("sign must be 1, -1 or 0"));
+                }
+                if !price.is_finite() {
+                    return Err(PyValueError::new_err("price must be a finite float"));
+                }
+                if !volume.is_finite() || volume < 0.0 {
+                    return Err(PyValueError::new_err("volume must be finite and >= 0"));
+                }
+            }
+            Message::Quote {
+                bid_price,
+                bid_size,
+                ask_price,
+                ask_size,
+                ..
+            } => {
+                if !bid_price.is_finite() || !ask_price.is_finite() {
+                    return Err(PyValueError::new_err(
+                        "bid and ask prices must be finite floats",
+                    ));
+                }
+                if !bid_size.is_finite()
+                    || bid_size < 0.0
+                    || !ask_size.is_finite()
+                    || ask_size < 0.0
+                {
+                    return Err(PyValueError::new_err(
+                        "bid and ask sizes must be finite and >= 0",
+                    ));
+                }
+            }
+            Message::System { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn event_code(value: f64) -> PyResult<i64> {
+        if !value.is_finite() || value.fract() != 0.0 {
+            return Err(PyValueError::new_err("system event codes must be integral"));
+        }
+        Ok(value as i64)
+    }
+
+    fn register(&mut self, name: &str, indicator: Indicator) -> PyResult<()> {
+        if self.nodes.iter().any(|node| node.name == name) {
+            return Err(PyValueError::new_err(format!(
+                "indicator '{name}' is already registered"
+            )));
+        }
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            indicator,
+            value: None,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// ITCH 5.0 prices carry four implied decimals.
+pub const ITCH_PRICE_SCALE: f64 = 10_000.0;
+/// Type, stock locate, tracking number and 6-byte nanosecond timestamp.
+const HEADER_LEN: usize = 11;
+
+/// One decoded order or trading-status message.
+///
+/// `side` is +1 buy / -1 sell (0 when unknown), `price` is NaN when unknown and
+/// `event_code` is only set for system events.
+pub struct ItchEvent {
+    pub timestamp: f64,
+    pub msg_type: u8,
+    pub stock_locate: u16,
+    pub order_ref: u64,
+    pub side: i64,
+    pub shares: f64,
+    pub price: f64,
+    pub event_code: u8,
+}
+
+/// A visible resting order; `price` is the raw integer ITCH price.
+struct RestingOrder {
+    side: i64,
+    price: u64,
+    shares: f64,
+}
+
+/// Decodes NASDAQ TotalView-ITCH 5.0 message bodies, tracking resting orders
+/// so executions, cancels and deletes carry a side and price.
+///
+/// The orders are also aggregated into price levels; the resulting top of book
+/// only describes a single symbol when decoding with a `stock` filter.
+pub struct ItchDecoder {
+    price_scale: f64,
+    stock: Option<[u8; 8]>,
+    stock_locate: Option<u16>,
+    orders: HashMap<u64, RestingOrder>,
+    bids: BTreeMap<u64, f64>,
+    asks: BTreeMap<u64, f64>,
+}
+
+impl ItchDecoder {
+    pub fn new(stock: Option<&str>, price_scale: f64) -> PyResult<Self> {
+        if !price_scale.is_finite() || price_scale <= 0.0 {
+            return Err(PyValueError::new_err(
+                "price_scale must be a positive, finite number",
+            ));
+        }
+        let stock = match stock {
+            Some(symbol) => {
+                if symbol.is_empty() || symbol.len() > 8 || !symbol.is_ascii() {
+                    return Err(PyValueError::new_err(
+                        "stock must be 1 to 8 ASCII characters",
+                    ));
+                }
+                // Symbols are left-justified and space-padded.
+                let mut padded = [b' '; 8];
+                padded[..symbol.len()].copy_from_slice(symbol.as_bytes());
+                Some(padded)
+            }
+            None => None,
+        };
+        Ok(Self {
+            price_scale,
+            stock,
+            stock_locate: None,
+            orders: HashMap::new(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        })
+    }
+
+    /// Best bid and ask as `(bid_price, bid_size, ask_price, ask_size)` once
+    /// both sides have resting orders.
+    pub fn top_of_book(&self) -> Option<(f64, f64, f64, f64)> {
+        let (&bid, &bid_size) = self.bids.last_key_value()?;
+        let (&ask, &ask_size) = self.asks.first_key_value()?;
+        Some((
+            bid as f64 / self.price_scale,
+            bid_size,
+            ask as f64 / self.price_scale,
+            ask_size,
+        ))
+    }
+
+    /// Normalize one message; types that carry no order or trading-status
+    /// information (and other stocks' messages when filtering) yield `None`.
+    /// Errors describe the malformed message; the caller knows where it was.
+    pub fn decode(&mut self, body: &[u8]) -> Result<Option<ItchEvent>, String> {
+        let Some(&msg_type) = body.first() else {
+            return Err("empty message".to_string());
+        };
+        let min_len = match msg_type {
+            b'S' => 12,
+            b'R' => 19,
+            b'A' => 36,
+            b'F' => 40,
+            b'E' => 31,
+            b'C' => 36,
+            b'X' => 23,
+            b'D' => 19,
+            b'U' => 35,
+            b'P' => 44,
+            _ => return Ok(None),
+        };
+        if body.len() < min_len {
+            return Err(format!(
+                "message '{}' is shorter than {min_len} bytes",
+                msg_type as char
+            ));
+        }
+        let stock_locate = u16::from_be_bytes([body[1], body[2]]);
+        let timestamp = be_uint(&body[5..HEADER_LEN]) as f64 / 1e9;
+        // Every kept type except system events starts with an order reference.
+        let order_ref = if msg_type == b'S' {
+            0
+        } else {
+            be_uint(&body[11..19])
+        };
+        let mut row = ItchEvent {
+            timestamp,
+            msg_type,
+            stock_locate,
+            order_ref,
+            side: 0,
+            shares: 0.0,
+            price: f64::NAN,
+            event_code: 0,
+        };
+
+        // Stock-scoped messages name the symbol; remember its locate code.
+        let symbol = match msg_type {
+            b'R' => Some(&body[11..19]),
+            b'A' | b'F' | b'P' => Some(&body[24..32]),
+            _ => None,
+        };
+        if let (Some(stock), Some(symbol)) = (self.stock, symbol) {
+            if symbol == stock {
+                self.stock_locate = Some(stock_locate);
+            }
+        }
+        if msg_type == b'R' {
+            return Ok(None);
+        }
+        if msg_type != b'S' && self.stock.is_some() && self.stock_locate != Some(stock_locate) {
+            return Ok(None);
+        }
+
+        match msg_type {
+            b'S' => row.event_code = body[11],
+            b'A' | b'F' | b'P' => {
+                let price = be_uint(&body[32..36]);
+                row.side = itch_side(body[19]);
+                row.shares = be_uint(&body[20..24]) as f64;
+                row.price = price as f64 / self.price_scale;
+                if msg_type != b'P' {
+                    self.rest(
+                        order_ref,
+                        RestingOrder {
+                            side: row.side,
+                            price,
+                            shares: row.shares,
+                        },
+                    );
+                }
+            }
+            b'E' | b'C' | b'X' => {
+                row.shares = be_uint(&body[19..23]) as f64;
+                if let Some(order) = self.take(order_ref, Some(row.shares)) {
+                    row.side = order.side;
+                    row.price = order.price as f64 / self.price_scale;
+                }
+                if msg_type == b'C' {
+                    row.price = be_uint(&body[32..36]) as f64 / self.price_scale;
+                }
+            }
+            b'D' => {
+                if let Some(order) = self.take(order_ref, None) {
+                    row.side = order.side;
+                    row.price = order.price as f64 / self.price_scale;
+                    row.shares = order.shares;
+                }
+            }
+            b'U' => {
+                // The replacement keeps the side under a new reference number.
+                let new_ref = be_uint(&body[19..27]);
+                let price = be_uint(&body[31..35]);
+                let side = self.take(order_ref, None).map_or(0, |order| order.side);
+                row.order_ref = new_ref;
+                row.side = side;
+                row.shares = be_uint(&body[27..31]) as f64;
+                row.price = price as f64 / self.price_scale;
+                self.rest(
+                    new_ref,
+                    RestingOrder {
+                        side,
+                        price,
+                        shares: row.shares,
+                    },
+                );
+            }
+            _ => unreachable!("filtered by the length table"),
+        }
+        Ok(Some(row))
+    }
+
+    fn rest(&mut self, order_ref: u64, order: RestingOrder) {
+        if let Some(levels) = self.levels_mut(order.side) {
+            *levels.entry(order.price).or_insert(0.0) += order.shares;
+        }
+        self.orders.insert(order_ref, order);
+    }
+
+    /// Remove `shares` (or everything left) from a resting order and its
+    /// level; returns the order's side and price with the shares taken.
+    fn take(&mut self, order_ref: u64, shares: Option<f64>) -> Option<RestingOrder> {
+        let order = self.orders.get_mut(&order_ref)?;
+        let taken = shares.map_or(order.shares, |shares| shares.min(order.shares));
+        order.shares -= taken;
+        let (side, price) = (order.side, order.price);
+        if order.shares <= 0.0 {
+            self.orders.remove(&order_ref);
+        }
+        if let Some(levels) = self.levels_mut(side) {
+            if let Some(level) = levels.get_mut(&price) {
+                *level -= taken;
+                if *level <= 0.0 {
+                    levels.remove(&price);
+                }
+            }
+        }
+        Some(RestingOrder {
+            side,
+            price,
+            shares: taken,
+        })
+    }
+
+    fn levels_mut(&mut self, side: i64) -> Option<&mut BTreeMap<u64, f64>> {
+        match side {
+            1 => Some(&mut self.bids),
+            -1 => Some(&mut self.asks),
+            _ => None,
+        }
+    }
+}
+
+fn be_uint(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0u64, |acc, &byte| (acc << 8) | u64::from(byte))
+}
+
+fn itch_side(indicator: u8) -> i64 {
+    match indicator {
+        b'B' => 1,
+        b'S' => -1,
+        _ => 0,
+    }
+}
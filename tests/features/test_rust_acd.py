@@ -0,0 +1,40 @@
+from __future__ import annotations
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustAcdModel = shijim_indicators.RustAcdModel
+
+
+def test_acd_recursion_and_surprise():
+    model = RustAcdModel(omega=0.2, alpha=0.1, beta=0.7)
+    # Unconditional mean duration: 0.2 / (1 - 0.8)
+    assert model.expected_duration() == pytest.approx(1.0)
+
+    assert model.update(10.0) is None
+
+    # Duration 2.0 against an expected 1.0.
+    assert model.update(12.0) == pytest.approx(2.0)
+    psi = 0.2 + 0.1 * 2.0 + 0.7 * 1.0
+    assert model.expected_duration() == pytest.approx(psi)
+    assert model.expected_intensity() == pytest.approx(1.0 / psi)
+
+    assert model.update(12.5) == pytest.approx(0.5 / psi)
+    psi = 0.2 + 0.1 * 0.5 + 0.7 * psi
+    assert model.expected_duration() == pytest.approx(psi)
+
+
+def test_acd_reset_and_validation():
+    with pytest.raises(ValueError):
+        RustAcdModel(omega=0.1, alpha=0.5, beta=0.5)
+    with pytest.raises(ValueError):
+        RustAcdModel(omega=0.0, alpha=0.1, beta=0.1)
+
+    model = RustAcdModel(omega=0.5, alpha=0.2, beta=0.3)
+    model.update(1.0)
+    model.update(4.0)
+    model.reset()
+    assert model.expected_duration() == pytest.approx(model.unconditional_duration())
+    assert model.update(0.0) is None
+    with pytest.raises(ValueError):
+        model.update(-1.0)
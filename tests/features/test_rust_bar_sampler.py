@@ -0,0 +1,89 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustBarSampler = shijim_indicators.RustBarSampler
+
+
+def test_dollar_bars_close_on_notional():
+    sampler = RustBarSampler("dollar", 1000.0)
+
+    assert sampler.update(100.0, 4.0, 1.0) is None
+    assert sampler.update(101.0, 3.0, 2.0) is None
+    bar = sampler.update(99.0, 4.0, 3.0)
+    assert bar is not None
+    start_ts, end_ts, open_, high, low, close, volume, vwap, trade_count = bar
+    assert start_ts == pytest.approx(1.0)
+    assert end_ts == pytest.approx(3.0)
+    assert (open_, high, low, close) == pytest.approx((100.0, 101.0, 99.0, 99.0))
+    assert volume == pytest.approx(11.0)
+    # (400 + 303 + 396) / 11
+    assert vwap == pytest.approx(1099.0 / 11.0)
+    assert trade_count == 3
+    assert sampler.pending_trade_count() == 0
+
+
+def test_imbalance_bars_use_explicit_signs_then_tick_rule():
+    sampler = RustBarSampler("imbalance", 10.0)
+
+    assert sampler.update(100.0, 6.0, 1.0, 1.0) is None
+    assert sampler.update(100.0, 2.0, 2.0, -1.0) is None
+    assert sampler.imbalance() == pytest.approx(4.0)
+    bar = sampler.update(100.0, 6.0, 3.0, 1.0)
+    assert bar[6] == pytest.approx(14.0)
+    assert bar[8] == 3
+    assert sampler.imbalance() == pytest.approx(0.0)
+
+    # Unsigned trades fall back to the tick rule: two down-ticks sell 10.
+    assert sampler.update(99.0, 5.0, 4.0) is None
+    assert sampler.imbalance() == pytest.approx(-5.0)
+    bar = sampler.update(98.0, 5.0, 5.0)
+    assert bar is not None
+    assert bar[5] == pytest.approx(98.0)
+
+
+def test_time_and_volume_modes_match_bar_builder():
+    sampler = RustBarSampler("time", 60.0)
+    builder = shijim_indicators.RustBarBuilder("time", 60.0)
+    ticks = [(100.0, 10.0, 0.0), (102.0, 5.0, 30.0), (99.0, 5.0, 59.0), (101.0, 1.0, 61.0)]
+    for price, volume, ts in ticks:
+        got = sampler.update(price, volume, ts)
+        expected = builder.update(price, volume, ts)
+        assert (got is None) == (expected is None)
+    assert got == pytest.approx(tuple(expected[f] for f in RustBarSampler.fields()))
+
+    sampler = RustBarSampler("volume", 20.0)
+    assert sampler.update(10.0, 25.0, 1.0)[6] == pytest.approx(25.0)
+
+
+def test_update_many_returns_record_array():
+    sampler = RustBarSampler("volume", 10.0)
+
+    bars = sampler.update_many(
+        np.array([10.0, 11.0, 12.0, 13.0, 14.0]),
+        np.array([5.0, 5.0, 4.0, 6.0, 1.0]),
+        np.array([1.0, 2.0, 3.0, 4.0, 5.0]),
+    )
+    assert list(bars.dtype.names) == RustBarSampler.fields()
+    assert len(bars) == 2
+    assert bars.open.tolist() == pytest.approx([10.0, 12.0])
+    assert bars.close.tolist() == pytest.approx([11.0, 13.0])
+    assert bars.trade_count.tolist() == [2, 2]
+    assert sampler.pending_trade_count() == 1
+    assert sampler.flush()[2] == pytest.approx(14.0)
+
+
+def test_bar_sampler_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustBarSampler("tick", 10.0)
+    with pytest.raises(ValueError):
+        RustBarSampler("dollar", -1.0)
+
+    sampler = RustBarSampler("imbalance", 5.0)
+    with pytest.raises(ValueError):
+        sampler.update(100.0, 1.0, 1.0, 2.0)
+    sampler.update(100.0, 1.0, 5.0)
+    with pytest.raises(ValueError):
+        sampler.update(100.0, 1.0, 4.0)
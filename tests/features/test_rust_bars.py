@@ -0,0 +1,69 @@
+from __future__ import annotations
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustBarBuilder = shijim_indicators.RustBarBuilder
+
+
+def test_time_bars_complete_on_interval_boundary():
+    builder = RustBarBuilder("time", 60.0)
+
+    assert builder.update(100.0, 10.0, 0.0) is None
+    assert builder.update(102.0, 5.0, 30.0) is None
+    assert builder.update(99.0, 5.0, 59.0) is None
+
+    bar = builder.update(101.0, 10.0, 61.0)
+    assert bar is not None
+    assert bar["start_ts"] == pytest.approx(0.0)
+    assert bar["end_ts"] == pytest.approx(59.0)
+    assert bar["open"] == pytest.approx(100.0)
+    assert bar["high"] == pytest.approx(102.0)
+    assert bar["low"] == pytest.approx(99.0)
+    assert bar["close"] == pytest.approx(99.0)
+    assert bar["volume"] == pytest.approx(20.0)
+    # (100*10 + 102*5 + 99*5) / 20
+    assert bar["vwap"] == pytest.approx(100.25)
+    assert bar["trade_count"] == 3
+
+    pending = builder.flush()
+    assert pending["start_ts"] == pytest.approx(60.0)
+    assert pending["open"] == pytest.approx(101.0)
+    assert pending["trade_count"] == 1
+    assert builder.flush() is None
+
+
+def test_volume_bars_complete_on_threshold():
+    builder = RustBarBuilder("volume", 20.0)
+
+    assert builder.update(10.0, 5.0, 1.0) is None
+    assert builder.update(11.0, 10.0, 2.0) is None
+    bar = builder.update(9.0, 5.0, 3.0)
+    assert bar["open"] == pytest.approx(10.0)
+    assert bar["high"] == pytest.approx(11.0)
+    assert bar["low"] == pytest.approx(9.0)
+    assert bar["close"] == pytest.approx(9.0)
+    assert bar["volume"] == pytest.approx(20.0)
+    # (10*5 + 11*10 + 9*5) / 20
+    assert bar["vwap"] == pytest.approx(10.25)
+    assert bar["trade_count"] == 3
+    assert builder.pending_trade_count() == 0
+
+    # A single large print fills a bar by itself.
+    bar = builder.update(12.0, 25.0, 4.0)
+    assert bar["volume"] == pytest.approx(25.0)
+    assert bar["trade_count"] == 1
+
+
+def test_bar_builder_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustBarBuilder("tick", 10.0)
+    with pytest.raises(ValueError):
+        RustBarBuilder("time", 0.0)
+
+    builder = RustBarBuilder("time", 1.0)
+    builder.update(100.0, 1.0, 5.0)
+    with pytest.raises(ValueError):
+        builder.update(100.0, 1.0, 4.0)
+    with pytest.raises(ValueError):
+        builder.update(100.0, -1.0, 6.0)
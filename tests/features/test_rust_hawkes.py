@@ -41,3 +41,70 @@ def test_hawkes_monotonic_time_validation():
 
     with pytest.raises(ValueError):
         calc.intensity_at(-np.inf)
+
+
+def test_hawkes_duplicate_timestamps():
+    permissive = RustHawkesIntensity(baseline=0.1, alpha=0.3, beta=2.0)
+    permissive.update(1.0)
+    # Equal timestamps stack excitation without decay by default.
+    assert permissive.update(1.0) == pytest.approx(0.1 + 0.3 + 0.3)
+
+    strict = RustHawkesIntensity(
+        baseline=0.1, alpha=0.3, beta=2.0, require_strictly_increasing=True
+    )
+    assert strict.requires_strictly_increasing()
+    strict.update(1.0)
+    with pytest.raises(ValueError):
+        strict.update(1.0)
+    # A rejected duplicate leaves the state untouched.
+    assert strict.current_intensity() == pytest.approx(0.4)
+    assert strict.update(1.5) == pytest.approx(0.1 + 0.3 * np.exp(-1.0) + 0.3)
+
+
+def _simulate_hawkes(baseline: float, alpha: float, beta: float, horizon: float, seed: int) -> np.ndarray:
+    """Ogata thinning for an exponential-kernel Hawkes process."""
+    rng = np.random.default_rng(seed)
+    t, last, excitation = 0.0, 0.0, 0.0
+    events = []
+    while True:
+        upper = baseline + excitation * np.exp(-beta * (t - last))
+        t += rng.exponential(1.0 / upper)
+        if t > horizon:
+            break
+        excitation *= np.exp(-beta * (t - last))
+        last = t
+        if rng.uniform() * upper <= baseline + excitation:
+            excitation += alpha
+            events.append(t)
+    return np.asarray(events, dtype=np.float64)
+
+
+def test_hawkes_mle_fit_recovers_parameters():
+    events = _simulate_hawkes(0.5, 0.8, 2.0, horizon=5000.0, seed=11)
+    calc = RustHawkesIntensity(baseline=1.0, alpha=0.1, beta=1.0)
+
+    true_ll = RustHawkesIntensity(0.5, 0.8, 2.0).log_likelihood(events, end_time=5000.0)
+    baseline, alpha, beta, ll = calc.fit(events, end_time=5000.0)
+    assert ll >= true_ll - 1e-6
+    assert baseline == pytest.approx(0.5, rel=0.25)
+    assert alpha == pytest.approx(0.8, rel=0.25)
+    assert beta == pytest.approx(2.0, rel=0.25)
+
+    # The calculator adopts the estimate and starts from a clean state.
+    assert calc.params() == pytest.approx((baseline, alpha, beta))
+    assert calc.current_intensity() == pytest.approx(baseline)
+    assert calc.log_likelihood(events, end_time=5000.0) == pytest.approx(ll)
+
+
+def test_hawkes_fit_options_and_validation():
+    events = _simulate_hawkes(0.5, 0.8, 2.0, horizon=500.0, seed=3)
+    calc = RustHawkesIntensity(baseline=1.0, alpha=0.1, beta=1.0)
+    calc.fit(events, update_params=False)
+    assert calc.params() == pytest.approx((1.0, 0.1, 1.0))
+
+    with pytest.raises(ValueError):
+        calc.fit(np.asarray([1.0], dtype=np.float64))
+    with pytest.raises(ValueError):
+        calc.fit(np.asarray([2.0, 1.0], dtype=np.float64))
+    with pytest.raises(ValueError):
+        calc.fit(np.asarray([1.0, 2.0], dtype=np.float64), end_time=1.5)
@@ -0,0 +1,66 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustKyleLambda = shijim_indicators.RustKyleLambda
+
+
+def test_kyle_lambda_recovers_linear_impact():
+    calc = RustKyleLambda(window_size=3)
+
+    # price_change = 0.01 + 0.002 * signed_volume
+    assert calc.update(100.0, 0.21) is None
+    assert calc.update(-50.0, -0.09) is None
+    assert calc.update(20.0, 0.05) == pytest.approx(0.002)
+    assert calc.intercept() == pytest.approx(0.01)
+
+    # Rolling: the first sample leaves the window.
+    assert calc.update(10.0, 0.03) == pytest.approx(0.002)
+    assert calc.samples_ready() == 3
+
+
+def test_kyle_lambda_rolling_ols_matches_numpy():
+    rng = np.random.default_rng(7)
+    volumes = rng.normal(0.0, 100.0, size=200)
+    changes = 0.003 * volumes + rng.normal(0.0, 0.05, size=200)
+
+    calc = RustKyleLambda(window_size=50)
+    results = calc.update_many(volumes, changes)
+    assert results[48] is None
+    for end in (50, 120, 200):
+        x = volumes[end - 50 : end]
+        y = changes[end - 50 : end]
+        expected = np.polyfit(x, y, 1)[0]
+        assert results[end - 1] == pytest.approx(expected, rel=1e-6)
+
+
+def test_kyle_lambda_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustKyleLambda(window_size=1)
+
+    calc = RustKyleLambda(window_size=2)
+    with pytest.raises(ValueError):
+        calc.update(float("nan"), 0.0)
+
+    # Zero variance in signed volume yields no estimate rather than a division error.
+    calc.update(5.0, 0.1)
+    assert calc.update(5.0, 0.2) is None
+
+
+def test_kyle_lambda_one_sided_flow_keeps_precision():
+    rng = np.random.default_rng(11)
+    # Persistent buying: signed volume sits far from zero.
+    volumes = 1e6 + rng.normal(0.0, 10.0, size=20_000)
+    changes = 0.003 * volumes + rng.normal(0.0, 0.05, size=20_000)
+
+    calc = RustKyleLambda(window_size=50)
+    results = calc.update_many(volumes, changes)
+    expected = np.polyfit(volumes[-50:], changes[-50:], 1)[0]
+    assert results[-1] == pytest.approx(expected, rel=1e-6)
+
+    flat = RustKyleLambda(window_size=3)
+    for change in (0.1, 0.2, 0.3):
+        flat.update(1e6, change)
+    assert flat.current_lambda() is None
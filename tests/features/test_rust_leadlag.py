@@ -0,0 +1,120 @@
+from __future__ import annotations
+
+import math
+import random
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustHayashiYoshida = shijim_indicators.RustHayashiYoshida
+
+
+def _naive_hy(obs0, obs1, lag=0.0):
+    """O(n^2) Hayashi-Yoshida sum over overlapping return intervals."""
+    intervals0 = [(t0, t1, r) for (t0, _), (t1, r) in zip(obs0, obs0[1:])]
+    intervals1 = [(t0 + lag, t1 + lag, r) for (t0, _), (t1, r) in zip(obs1, obs1[1:])]
+    return sum(
+        ra * rb
+        for a0, a1, ra in intervals0
+        for b0, b1, rb in intervals1
+        if a0 < b1 and b0 < a1
+    )
+
+
+def _observe(path, times, delay=0.0):
+    """Returns of the step path sampled at `times`, optionally delayed."""
+    obs, last = [], None
+    for t in times:
+        value = path(t - delay)
+        obs.append((t, 0.0 if last is None else value - last))
+        last = value
+    return obs
+
+
+def _random_walk(rng, horizon, step=0.1):
+    increments = [rng.gauss(0.0, 1.0) for _ in range(int(horizon / step) + 1)]
+    levels = [0.0]
+    for inc in increments:
+        levels.append(levels[-1] + inc)
+    return lambda t: levels[max(0, int(t / step))]
+
+
+def _feed(hy, obs0, obs1):
+    events = [(t, 0, r) for t, r in obs0] + [(t, 1, r) for t, r in obs1]
+    for t, instrument, r in sorted(events):
+        hy.update(instrument, t, r)
+
+
+def test_matches_naive_estimator_on_asynchronous_ticks():
+    rng = random.Random(7)
+    path = _random_walk(rng, 100.0)
+    times0 = sorted(rng.sample(range(1, 1000), 120))
+    times1 = sorted(rng.sample(range(1, 1000), 80))
+    obs0 = _observe(path, [t / 10 for t in times0])
+    obs1 = _observe(path, [t / 10 for t in times1])
+
+    hy = RustHayashiYoshida()
+    _feed(hy, obs0, obs1)
+
+    expected = _naive_hy(obs0, obs1)
+    assert hy.covariance() == pytest.approx(expected)
+    rv0 = sum(r * r for _, r in obs0[1:])
+    rv1 = sum(r * r for _, r in obs1[1:])
+    assert hy.realized_variances() == pytest.approx((rv0, rv1))
+    assert hy.correlation() == pytest.approx(expected / math.sqrt(rv0 * rv1))
+    assert hy.correlation() > 0.5
+
+
+def test_lagged_covariances_detect_leader():
+    rng = random.Random(11)
+    path = _random_walk(rng, 120.0)
+    times0 = [t / 10 for t in sorted(rng.sample(range(30, 1100), 200))]
+    times1 = [t / 10 for t in sorted(rng.sample(range(30, 1100), 150))]
+    # Instrument 0 sees the path two seconds after instrument 1.
+    obs0 = _observe(path, times0, delay=2.0)
+    obs1 = _observe(path, times1)
+
+    lags = [-2.0, -1.0, 0.0, 1.0, 2.0, 3.0]
+    hy = RustHayashiYoshida(lags=lags)
+    _feed(hy, obs0, obs1)
+
+    assert hy.lags() == lags
+    for lag, cov in zip(lags, hy.covariances()):
+        assert cov == pytest.approx(_naive_hy(obs0, obs1, lag))
+    assert hy.lead_lag() == 2.0
+    assert hy.correlations()[4] > 0.5
+
+
+def test_hayashi_yoshida_validation_and_reset():
+    with pytest.raises(ValueError):
+        RustHayashiYoshida(lags=[])
+    hy = RustHayashiYoshida()
+    with pytest.raises(ValueError):
+        hy.update(2, 0.0, 0.1)
+    assert hy.update(0, 1.0, 0.5) is None
+    with pytest.raises(ValueError):
+        hy.update(0, 1.0, 0.1)
+
+    hy.update(1, 0.5, 0.0)
+    hy.update(0, 2.0, 0.1)
+    assert hy.update(1, 3.0, 0.2) == pytest.approx(1.0)
+    assert hy.covariance() == pytest.approx(0.02)
+    hy.reset()
+    assert hy.covariance() == 0.0
+    assert hy.correlation() is None
+    assert hy.lead_lag() is None
+
+
+def test_one_sided_feed_keeps_buffer_bounded():
+    hy = RustHayashiYoshida(lags=[-1.0, 0.0, 1.0])
+    for i in range(10_000):
+        hy.update(0, i * 0.1, 0.01)
+    # Only the intervals within the widest lag of the latest observation.
+    assert hy.buffered_intervals() <= 11
+    assert hy.covariances() == [0.0, 0.0, 0.0]
+
+    # The other side joining later still pairs with the retained intervals,
+    # here the one it overlaps once shifted back by a second.
+    hy.update(1, 999.9, 0.0)
+    hy.update(1, 1000.0, 0.5)
+    assert hy.covariances() == pytest.approx([0.5 * 0.01, 0.0, 0.0])
@@ -0,0 +1,199 @@
+from __future__ import annotations
+
+import struct
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustPipeline = shijim_indicators.RustPipeline
+
+# time, type, order_id, size, price, direction
+MESSAGES = """\
+34200.0,1,1,100,1000000,1
+34200.5,1,2,50,1001000,-1
+34201.0,4,2,20,1001000,-1
+34201.5,7,0,0,-1,-1
+34202.0,4,1,10,1000000,1
+"""
+# ask_price, ask_size, bid_price, bid_size
+ORDERBOOK = """\
+9999999999,0,1000000,100
+1001000,50,1000000,100
+1001000,30,1000000,100
+1001000,30,1000000,100
+1001000,30,1000000,90
+"""
+
+
+def _write(tmp_path):
+    messages = tmp_path / "messages.csv"
+    orderbook = tmp_path / "orderbook.csv"
+    messages.write_text(MESSAGES)
+    orderbook.write_text(ORDERBOOK)
+    return str(messages), str(orderbook)
+
+
+def test_parse_lobster_csv_columns(tmp_path):
+    messages, orderbook = _write(tmp_path)
+
+    data = shijim_indicators.parse_lobster_csv(messages, orderbook)
+    assert data["time"].tolist() == pytest.approx([34200.0, 34200.5, 34201.0, 34201.5, 34202.0])
+    assert data["event_type"].tolist() == [1, 1, 4, 7, 4]
+    assert data["price"][0] == pytest.approx(100.0)
+    assert data["direction"].tolist() == [1, -1, -1, -1, 1]
+    # The empty ask level on the first row is reported as NaN / 0.
+    assert data["ask_size"][0] == 0.0
+    assert data["ask_price"][1] == pytest.approx(100.1)
+    assert data["bid_size"].tolist() == pytest.approx([100.0, 100.0, 100.0, 100.0, 90.0])
+
+    assert "bid_price" not in shijim_indicators.parse_lobster_csv(messages)
+
+
+def test_run_through_pipeline(tmp_path):
+    messages, orderbook = _write(tmp_path)
+    pipeline = RustPipeline()
+    pipeline.add_vpin("vpin", 10.0, 1)
+    pipeline.add_ofi("ofi")
+    pipeline.add_hawkes("trades", 0.1, 0.5, 1.0)
+
+    latest = shijim_indicators.run_through_pipeline(messages, pipeline, orderbook)
+    # Last top-of-book change: bid size 100 -> 90.
+    assert latest["ofi"] == pytest.approx(-10.0)
+    # The executed sell limit order (direction -1) was a buy; the final one a sell.
+    assert latest["vpin"] == pytest.approx(1.0)
+    assert pipeline.sessions_rolled() == 0
+    assert latest == pipeline.latest()
+
+
+def test_halt_resume_keeps_session_state(tmp_path):
+    messages = tmp_path / "halt.csv"
+    messages.write_text(
+        "34200.0,4,1,10,1000000,-1\n"
+        "34201.0,7,0,0,-1,-1\n"
+        "34202.0,7,0,0,1,-1\n"
+        "34203.0,4,2,10,1000000,1\n"
+    )
+    pipeline = RustPipeline()
+    pipeline.add_vpin("vpin", 10.0, 2)
+
+    latest = shijim_indicators.run_through_pipeline(str(messages), pipeline)
+    # The resume is not a session open, so both buckets stay in the window.
+    assert pipeline.sessions_rolled() == 0
+    assert latest["vpin"] == pytest.approx(1.0)
+    assert RustPipeline.EVENT_RESUME not in pipeline.session_events()
+
+
+def test_lobster_loader_errors(tmp_path):
+    messages, _ = _write(tmp_path)
+    short_book = tmp_path / "short.csv"
+    short_book.write_text("1001000,50,1000000,100\n")
+    with pytest.raises(ValueError):
+        shijim_indicators.run_through_pipeline(messages, RustPipeline(), str(short_book))
+    long_book = tmp_path / "long.csv"
+    long_book.write_text(ORDERBOOK + "1001000,30,1000000,90\n")
+    with pytest.raises(ValueError, match="longer"):
+        shijim_indicators.run_through_pipeline(messages, RustPipeline(), str(long_book))
+
+    bad = tmp_path / "bad.csv"
+    bad.write_text("34200.0,1,x,100,1000000,1\n")
+    with pytest.raises(ValueError):
+        shijim_indicators.run_through_pipeline(str(bad), RustPipeline())
+    with pytest.raises(OSError):
+        shijim_indicators.run_through_pipeline(str(tmp_path / "missing.csv"), RustPipeline())
+
+
+def _itch(msg_type, locate, ts_ns, payload):
+    body = msg_type + struct.pack(">HH", locate, 0) + ts_ns.to_bytes(6, "big") + payload
+    return struct.pack(">H", len(body)) + body
+
+
+def _write_itch(tmp_path):
+    def stock(symbol):
+        return symbol.ljust(8).encode()
+
+    directory = stock("AAPL") + b"N" + b"\0" * 20
+    messages = [
+        _itch(b"S", 0, 1_000, b"Q"),
+        _itch(b"R", 7, 2_000, directory),
+        _itch(b"R", 9, 2_000, stock("MSFT") + b"N" + b"\0" * 20),
+        # Add buy 100 @ 150.25, then a MSFT add that the filter drops.
+        _itch(b"A", 7, 3_000, struct.pack(">QcI", 11, b"B", 100) + stock("AAPL") + struct.pack(">I", 1_502_500)),
+        _itch(b"A", 9, 3_500, struct.pack(">QcI", 12, b"S", 10) + stock("MSFT") + struct.pack(">I", 3_000_000)),
+        # Execute 30, replace with ref 13 for 50 @ 150.20, then delete it.
+        _itch(b"E", 7, 4_000, struct.pack(">QIQ", 11, 30, 1)),
+        _itch(b"U", 7, 5_000, struct.pack(">QQII", 11, 13, 50, 1_502_000)),
+        _itch(b"X", 7, 5_500, struct.pack(">QI", 13, 20)),
+        _itch(b"D", 7, 6_000, struct.pack(">Q", 13)),
+        _itch(b"P", 7, 7_000, struct.pack(">QcI", 0, b"S", 5) + stock("AAPL") + struct.pack(">IQ", 1_501_000, 2)),
+    ]
+    path = tmp_path / "itch.bin"
+    path.write_bytes(b"".join(messages))
+    return str(path)
+
+
+def test_parse_itch_file_tracks_orders(tmp_path):
+    path = _write_itch(tmp_path)
+
+    data = shijim_indicators.parse_itch_file(path, stock="AAPL")
+    assert bytes(data["msg_type"].tolist()) == b"SAEUXDP"
+    assert data["timestamp"][1] == pytest.approx(3e-6)
+    assert data["event_code"][0] == ord("Q")
+    # Executions, cancels and deletes inherit the resting order's side and price.
+    assert data["side"].tolist() == [0, 1, 1, 1, 1, 1, -1]
+    assert data["price"][1:].tolist() == pytest.approx([150.25, 150.25, 150.2, 150.2, 150.2, 150.1])
+    assert data["shares"].tolist() == pytest.approx([0.0, 100.0, 30.0, 50.0, 20.0, 30.0, 5.0])
+    assert data["order_ref"].tolist()[3] == 13
+
+    unfiltered = shijim_indicators.parse_itch_file(path)
+    assert len(unfiltered["msg_type"]) == 8
+
+
+def test_run_itch_through_pipeline(tmp_path):
+    aapl = b"AAPL".ljust(8)
+    messages = [
+        _itch(b"S", 0, 1_000, b"Q"),
+        _itch(b"R", 7, 1_500, aapl + b"N" + b"\0" * 20),
+        _itch(b"A", 7, 2_000, struct.pack(">QcI", 1, b"B", 100) + aapl + struct.pack(">I", 1_000_000)),
+        # The book becomes two-sided: 100 @ 100.00 / 200 @ 100.10.
+        _itch(b"A", 7, 3_000, struct.pack(">QcI", 2, b"S", 200) + aapl + struct.pack(">I", 1_001_000)),
+        # A buyer lifts 50 of the resting offer.
+        _itch(b"E", 7, 4_000, struct.pack(">QIQ", 2, 50, 1)),
+        _itch(b"A", 7, 5_000, struct.pack(">QcI", 3, b"B", 40) + aapl + struct.pack(">I", 1_000_500)),
+        # A hidden resting buy is hit for 10.
+        _itch(b"P", 7, 6_000, struct.pack(">QcI", 0, b"B", 10) + aapl + struct.pack(">IQ", 1_000_200, 1)),
+        _itch(b"S", 0, 7_000, b"M"),
+    ]
+    path = tmp_path / "session.bin"
+    path.write_bytes(b"".join(messages))
+
+    pipeline = RustPipeline()
+    pipeline.add_vpin("vpin", 60.0, 1)
+    pipeline.add_ofi("ofi")
+    pipeline.add_hawkes("trades", 0.1, 0.5, 1.0)
+    latest = shijim_indicators.run_itch_through_pipeline(str(path), pipeline, "AAPL")
+
+    # Buy 50 and sell 10 fill one bucket.
+    assert latest["vpin"] == pytest.approx(40.0 / 60.0)
+    # Last book change: the bid steps up to 40 @ 100.05.
+    assert latest["ofi"] == pytest.approx(40.0)
+    assert latest["trades"] is not None
+    # Only the market open rolls the session; the close does not.
+    assert pipeline.sessions_rolled() == 1
+    assert latest == pipeline.latest()
+
+    with pytest.raises(ValueError):
+        shijim_indicators.run_itch_through_pipeline(str(path), RustPipeline(), "TOO_LONG_SYMBOL")
+
+
+def test_parse_itch_file_errors(tmp_path):
+    truncated = tmp_path / "truncated.bin"
+    truncated.write_bytes(_itch(b"D", 7, 1, struct.pack(">Q", 1))[:-3])
+    with pytest.raises(ValueError, match="truncated"):
+        shijim_indicators.parse_itch_file(str(truncated))
+    # A lone length byte after the last message is a cut-off prefix, not EOF.
+    split_prefix = tmp_path / "split_prefix.bin"
+    split_prefix.write_bytes(_itch(b"S", 0, 1, b"Q") + b"\x00")
+    with pytest.raises(ValueError, match="truncated"):
+        shijim_indicators.parse_itch_file(str(split_prefix))
+    with pytest.raises(ValueError):
+        shijim_indicators.parse_itch_file(str(truncated), stock="TOO_LONG_SYMBOL")
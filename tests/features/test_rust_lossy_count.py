@@ -0,0 +1,53 @@
+from __future__ import annotations
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustLossyCounter = shijim_indicators.RustLossyCounter
+
+
+def _skewed_stream() -> list[int]:
+    # 500x id 1, 300x id 2, 100x id 3 and 200 singletons, interleaved.
+    hot = [1] * 500 + [2] * 300 + [3] * 100
+    cold = list(range(1000, 1200))
+    stream: list[int] = []
+    for i, sec_id in enumerate(hot):
+        stream.append(sec_id)
+        if i % 4 == 0 and cold:
+            stream.append(cold.pop())
+    stream.extend(cold)
+    return stream
+
+
+def test_lossy_counter_finds_heavy_hitters():
+    counter = RustLossyCounter(10)
+    stream = _skewed_stream()
+    for sec_id in stream:
+        counter.update(sec_id)
+
+    assert counter.total_count() == len(stream)
+    bound = counter.error_bound()
+    assert bound == len(stream) // 11
+
+    top = counter.top_k(2)
+    assert [sec_id for sec_id, _ in top] == [1, 2]
+    for sec_id, true_count in ((1, 500), (2, 300)):
+        estimate = counter.estimate(sec_id)
+        assert true_count - bound <= estimate <= true_count
+
+    assert len(counter.top_k(100)) <= 10
+
+
+def test_lossy_counter_reset_and_validation():
+    with pytest.raises(ValueError):
+        RustLossyCounter(0)
+
+    counter = RustLossyCounter(2)
+    for sec_id in (7, 7, 8, 9):
+        counter.update(sec_id)
+    # 9 evicts one unit from every counter: 7 -> 1, 8 -> 0 (dropped).
+    assert counter.top_k(5) == [(7, 1)]
+
+    counter.reset()
+    assert counter.total_count() == 0
+    assert counter.top_k(5) == []
@@ -0,0 +1,61 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustMicroprice = shijim_indicators.RustMicroprice
+
+
+def _vec(values: list[float]) -> np.ndarray:
+    return np.asarray(values, dtype=np.float64)
+
+
+def test_microprice_imbalance_and_weighted_mid():
+    calc = RustMicroprice(levels=2)
+    value = calc.update_from_levels(
+        _vec([100.0, 99.0]), _vec([10.0, 30.0]), _vec([101.0, 102.0]), _vec([30.0, 20.0])
+    )
+    # (100 * 30 + 101 * 10) / 40
+    assert value == pytest.approx(100.25)
+    assert calc.imbalance() == pytest.approx(-0.5)
+    # bid vwap 99.25 over 40, ask vwap 101.4 over 50
+    assert calc.weighted_mid() == pytest.approx((99.25 * 50.0 + 101.4 * 40.0) / 90.0)
+
+
+def test_microprice_ema_smoothing():
+    calc = RustMicroprice(smoothing=0.5)
+    calc.update_from_levels(_vec([100.0]), _vec([10.0]), _vec([101.0]), _vec([30.0]))
+    smoothed = calc.update_from_levels(_vec([100.0]), _vec([30.0]), _vec([101.0]), _vec([10.0]))
+    assert smoothed == pytest.approx(100.25 + 0.5 * (100.75 - 100.25))
+    assert calc.imbalance() == pytest.approx(0.0)
+    assert calc.raw_values() == pytest.approx((100.75, 0.5, 100.75))
+
+    # One side missing: no update, state preserved.
+    assert calc.update_from_levels(_vec([]), _vec([]), _vec([101.0]), _vec([10.0])) is None
+    assert calc.microprice() == pytest.approx(smoothed)
+
+
+def test_microprice_top_series_matches_sequential():
+    bids = _vec([100.0, 100.0, 100.5])
+    bid_sizes = _vec([10.0, 30.0, 5.0])
+    asks = _vec([101.0, 101.0, 101.0])
+    ask_sizes = _vec([30.0, 10.0, 15.0])
+
+    batch = RustMicroprice(smoothing=0.3).update_top_series(bids, bid_sizes, asks, ask_sizes)
+    calc = RustMicroprice(smoothing=0.3)
+    sequential = [
+        calc.update_from_levels(_vec([b]), _vec([bs]), _vec([a]), _vec([as_]))
+        for b, bs, a, as_ in zip(bids, bid_sizes, asks, ask_sizes)
+    ]
+    assert batch == pytest.approx(sequential)
+
+
+def test_microprice_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustMicroprice(levels=0)
+    with pytest.raises(ValueError):
+        RustMicroprice(smoothing=0.0)
+    calc = RustMicroprice()
+    with pytest.raises(ValueError):
+        calc.update_from_levels(_vec([100.0]), _vec([]), _vec([101.0]), _vec([1.0]))
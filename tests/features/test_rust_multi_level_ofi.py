@@ -0,0 +1,50 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustMultiLevelOfi = shijim_indicators.RustMultiLevelOfi
+RustOfiCalculator = shijim_indicators.RustOfiCalculator
+
+
+def _vec(values: list[float]) -> np.ndarray:
+    return np.asarray(values, dtype=np.float64)
+
+
+BOOK_1 = (_vec([100.0, 99.0]), _vec([10.0, 20.0]), _vec([101.0, 102.0]), _vec([10.0, 20.0]))
+BOOK_2 = (_vec([100.0, 99.0]), _vec([15.0, 10.0]), _vec([101.0, 102.0]), _vec([5.0, 30.0]))
+
+
+def test_multi_level_ofi_decay_weights():
+    calc = RustMultiLevelOfi(depth=2, decay=0.5)
+    assert calc.weights() == pytest.approx([1.0, 0.5])
+    assert calc.update_from_levels(*BOOK_1) is None
+
+    # level 0: (15 - 10) - (5 - 10) = 10; level 1: (10 - 20) - (30 - 20) = -20
+    assert calc.update_from_levels(*BOOK_2) == pytest.approx(10.0 + 0.5 * -20.0)
+    np.testing.assert_allclose(calc.level_flows(), [10.0, -20.0])
+
+
+def test_multi_level_ofi_explicit_weights_and_single_level_parity():
+    calc = RustMultiLevelOfi(depth=2, weights=[1.0, 2.0])
+    calc.update_from_levels(*BOOK_1)
+    assert calc.update_from_levels(*BOOK_2) == pytest.approx(10.0 - 40.0)
+
+    # With a single level it reduces to the best-level calculator.
+    single = RustMultiLevelOfi(depth=1)
+    best = RustOfiCalculator()
+    for book in (BOOK_1, BOOK_2):
+        assert single.update_from_levels(*book) == best.update_from_levels(*book)
+
+
+def test_multi_level_ofi_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustMultiLevelOfi(depth=0)
+    with pytest.raises(ValueError):
+        RustMultiLevelOfi(depth=2, weights=[1.0])
+    with pytest.raises(ValueError):
+        RustMultiLevelOfi(depth=2, decay=-1.0)
+    calc = RustMultiLevelOfi(depth=2)
+    with pytest.raises(ValueError):
+        calc.update_from_levels(_vec([100.0, 99.0]), _vec([1.0]), _vec([101.0]), _vec([1.0]))
@@ -0,0 +1,66 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustHawkesIntensity = shijim_indicators.RustHawkesIntensity
+RustMultivariateHawkes = shijim_indicators.RustMultivariateHawkes
+
+
+def _arr(values) -> np.ndarray:
+    return np.asarray(values, dtype=np.float64)
+
+
+def test_multivariate_hawkes_cross_excitation():
+    # Type 0 = buys, type 1 = sells; alpha[i][j] is the jump in i caused by j.
+    calc = RustMultivariateHawkes(
+        _arr([0.2, 0.3]),
+        _arr([[0.5, 0.1], [0.4, 0.6]]),
+        _arr([1.0, 2.0]),
+    )
+    assert calc.dimension() == 2
+
+    np.testing.assert_allclose(calc.update(0, 0.0), [0.2 + 0.5, 0.3 + 0.4])
+
+    after_sell = calc.update(1, 1.0)
+    expected = [
+        0.2 + 0.5 * np.exp(-1.0) + 0.1,
+        0.3 + 0.4 * np.exp(-2.0) + 0.6,
+    ]
+    np.testing.assert_allclose(after_sell, expected)
+
+    # Querying does not mutate state.
+    at_two = calc.intensities_at(2.0)
+    np.testing.assert_allclose(
+        at_two,
+        [0.2 + (expected[0] - 0.2) * np.exp(-1.0), 0.3 + (expected[1] - 0.3) * np.exp(-2.0)],
+    )
+    np.testing.assert_allclose(calc.current_intensities(), expected)
+
+
+def test_multivariate_hawkes_single_type_matches_univariate():
+    multi = RustMultivariateHawkes(_arr([0.2]), _arr([[0.8]]), _arr([1.5]))
+    uni = RustHawkesIntensity(baseline=0.2, alpha=0.8, beta=1.5)
+    for ts in (0.0, 1.0, 1.25, 4.0):
+        assert multi.update(0, ts)[0] == pytest.approx(uni.update(ts))
+
+    multi.reset()
+    multi.update_many(np.asarray([0, 0], dtype=np.int64), _arr([0.0, 1.0]))
+    uni.reset()
+    uni.update(0.0)
+    assert multi.current_intensities()[0] == pytest.approx(uni.update(1.0))
+
+
+def test_multivariate_hawkes_validation():
+    with pytest.raises(ValueError):
+        RustMultivariateHawkes(_arr([0.1, 0.1]), _arr([[0.1]]), _arr([1.0, 1.0]))
+    with pytest.raises(ValueError):
+        RustMultivariateHawkes(_arr([0.1]), _arr([[0.1]]), _arr([0.0]))
+
+    calc = RustMultivariateHawkes(_arr([0.1]), _arr([[0.1]]), _arr([1.0]))
+    with pytest.raises(ValueError):
+        calc.update(1, 0.0)
+    calc.update(0, 1.0)
+    with pytest.raises(ValueError):
+        calc.update(0, 0.5)
@@ -0,0 +1,122 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+
+
+def _vec(values: list[float]) -> np.ndarray:
+    return np.asarray(values, dtype=np.float64)
+
+
+def _classifier():
+    clf = shijim_indicators.RustTradeClassifier()
+    clf.update_quote(100.0, 101.0, 0.0)
+    return clf
+
+
+# (factory, list method, arguments, warm-up rows that have no value yet); the
+# `<method>_np` twin must return the same values as float64 with NaN warm-up.
+CASES = [
+    pytest.param(
+        lambda: shijim_indicators.RustRollingStats(window_size=4),
+        "update_many",
+        (_vec([3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0]),),
+        3,
+        id="rolling",
+    ),
+    pytest.param(
+        lambda: shijim_indicators.RustVpinCalculator(bucket_volume=50.0, window_size=2),
+        "update_signed_series",
+        (_vec([20.0, 30.0, -40.0, -20.0, 40.0, 10.0]),),
+        3,
+        id="vpin",
+    ),
+    pytest.param(
+        lambda: shijim_indicators.RustAcdModel(omega=0.2, alpha=0.1, beta=0.7),
+        "update_many",
+        (_vec([10.0, 12.0, 12.5, 14.0]),),
+        1,
+        id="acd",
+    ),
+    pytest.param(
+        lambda: shijim_indicators.RustHawkesIntensity(baseline=0.2, alpha=0.8, beta=1.5),
+        "update_many",
+        (_vec([0.0, 0.5, 1.0, 3.0]),),
+        0,
+        id="hawkes",
+    ),
+    pytest.param(
+        lambda: shijim_indicators.RustKyleLambda(window_size=3),
+        "update_many",
+        (_vec([100.0, -50.0, 20.0, 10.0, -30.0]), _vec([0.21, -0.09, 0.05, 0.03, -0.04])),
+        2,
+        id="kyle_lambda",
+    ),
+    pytest.param(
+        lambda: shijim_indicators.RustMicroprice(smoothing=0.3),
+        "update_top_series",
+        (
+            _vec([100.0, 100.0, 100.5]),
+            _vec([10.0, 30.0, 5.0]),
+            _vec([101.0, 101.0, 101.0]),
+            _vec([30.0, 10.0, 15.0]),
+        ),
+        0,
+        id="microprice",
+    ),
+    pytest.param(
+        lambda: shijim_indicators.RustRealizedVolatility(window_size=3),
+        "update_many",
+        (_vec([100.0, 101.0, 100.0, 102.0, 101.0]), _vec([0.0, 1.0, 2.0, 3.0, 4.0])),
+        3,
+        id="rv",
+    ),
+    pytest.param(
+        _classifier,
+        "classify_many",
+        (_vec([100.8, 100.1, 100.5, 100.5]), _vec([1.0, 2.0, 3.0, 4.0]), _vec([1.0, 2.0, 3.0, 4.0])),
+        0,
+        id="trade_sign",
+    ),
+    pytest.param(
+        # Trades before the anchor leave the session empty.
+        lambda: shijim_indicators.RustVwapCalculator(100.0, anchor_ts=10.0),
+        "update_many",
+        (_vec([100.0, 110.0, 120.0, 130.0]), _vec([1.0, 2.0, 1.0, 1.0]), _vec([5.0, 8.0, 12.0, 15.0])),
+        2,
+        id="vwap",
+    ),
+    pytest.param(
+        lambda: shijim_indicators.RustRollingCorrelation(window_size=3),
+        "update_many",
+        (_vec([1.0, 3.0, 2.0, 5.0, 4.0]), _vec([2.0, 1.0, 4.0, 3.0, 6.0])),
+        2,
+        id="pairwise",
+    ),
+    pytest.param(
+        shijim_indicators.RustHayashiYoshida,
+        "update_many",
+        (
+            np.asarray([0, 1, 0, 1, 0, 1], dtype=np.int64),
+            _vec([0.0, 0.5, 1.0, 1.5, 2.0, 2.5]),
+            _vec([0.0, 0.0, 0.2, 0.1, -0.1, 0.3]),
+        ),
+        3,
+        id="leadlag",
+    ),
+]
+
+
+@pytest.mark.parametrize(("factory", "method", "args", "warmup"), CASES)
+def test_np_variant_matches_list(factory, method, args, warmup):
+    listed = getattr(factory(), method)(*args)
+    array = getattr(factory(), f"{method}_np")(*args)
+
+    assert isinstance(array, np.ndarray)
+    assert array.dtype == np.float64
+    assert listed[:warmup] == [None] * warmup
+    assert np.isnan(array[:warmup]).all()
+    assert not np.isnan(array[warmup:]).any()
+    assert array[warmup:].tolist() == pytest.approx(listed[warmup:])
@@ -54,3 +54,52 @@ def test_ofi_length_mismatch():
     calc.update_from_levels(_vec([100.0]), _vec([5.0]), _vec([101.0]), _vec([5.0]))
     with pytest.raises(ValueError):
         calc.update_from_levels(_vec([100.0, 99.0]), _vec([5.0]), _vec([101.0]), _vec([5.0]))
+
+
+def test_ofi_update_with_status_distinguishes_no_signal_cases():
+    calc = RustOfiCalculator()
+
+    status, value = calc.update_with_status(_vec([100.0]), _vec([10.0]), _vec([101.0]), _vec([10.0]))
+    assert (status, value) == ("warming_up", None)
+
+    status, value = calc.update_with_status(_vec([100.0]), _vec([10.0]), _vec([101.0]), _vec([10.0]))
+    assert status == "value"
+    assert value == pytest.approx(0.0)
+
+    status, value = calc.update_with_status(_vec([]), _vec([]), _vec([101.0]), _vec([10.0]))
+    assert (status, value) == ("missing_depth", None)
+
+    # After missing depth the calculator has no full book to diff against.
+    status, value = calc.update_with_status(_vec([100.0]), _vec([10.0]), _vec([101.0]), _vec([10.0]))
+    assert (status, value) == ("warming_up", None)
+
+    status, value = calc.update_with_status(_vec([100.5]), _vec([5.0]), _vec([101.0]), _vec([10.0]))
+    assert status == "value"
+    assert value == pytest.approx(5.0)
+
+
+def test_ofi_update_series_matches_per_snapshot_updates():
+    bid_prices = np.array([[100.0, 99.5], [100.5, 100.0], [100.5, 100.0], [100.0, 99.5]])
+    bid_sizes = np.array([[10.0, 4.0], [5.0, 10.0], [8.0, 10.0], [3.0, 2.0]])
+    ask_prices = np.array([[101.0], [101.0], [100.8], [100.8]])
+    ask_sizes = np.array([[10.0], [10.0], [20.0], [15.0]])
+
+    series = RustOfiCalculator().update_series(bid_prices, bid_sizes, ask_prices, ask_sizes)
+    assert isinstance(series, np.ndarray)
+    assert series.shape == (4,)
+    assert np.isnan(series[0])
+
+    calc = RustOfiCalculator()
+    expected = [
+        calc.update_from_levels(bid_prices[i], bid_sizes[i], ask_prices[i], ask_sizes[i])
+        for i in range(4)
+    ]
+    assert series[1:].tolist() == pytest.approx(expected[1:])
+
+
+def test_ofi_update_series_shape_mismatch():
+    calc = RustOfiCalculator()
+    with pytest.raises(ValueError):
+        calc.update_series(np.ones((3, 2)), np.ones((3, 1)), np.ones((3, 1)), np.ones((3, 1)))
+    with pytest.raises(ValueError):
+        calc.update_series(np.ones((3, 1)), np.ones((3, 1)), np.ones((2, 1)), np.ones((2, 1)))
@@ -0,0 +1,105 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustOrderBook = shijim_indicators.RustOrderBook
+
+
+def _book() -> RustOrderBook:
+    book = RustOrderBook(tick_size=0.5)
+    book.add("bid", 100.0, 10.0)
+    book.add("bid", 99.5, 20.0)
+    book.add("bid", 99.0, 30.0)
+    book.add("ask", 100.5, 30.0)
+    book.add("ask", 101.0, 5.0)
+    return book
+
+
+def test_order_book_incremental_updates():
+    book = _book()
+    assert book.best_bid() == pytest.approx((100.0, 10.0))
+    assert book.best_ask() == pytest.approx((100.5, 30.0))
+
+    book.add("bid", 100.0, 5.0)
+    assert book.best_bid() == pytest.approx((100.0, 15.0))
+
+    book.modify("ask", 100.5, 12.0)
+    assert book.best_ask() == pytest.approx((100.5, 12.0))
+
+    # A trade that exhausts the best ask removes the level.
+    book.trade("ask", 100.5, 12.0)
+    assert book.best_ask() == pytest.approx((101.0, 5.0))
+
+    book.delete("bid", 100.0)
+    assert book.best_bid() == pytest.approx((99.5, 20.0))
+    assert book.level_count("bid") == 2
+
+    book.reset()
+    assert book.best_bid() is None
+    assert book.microprice() is None
+
+
+def test_order_book_microprice_and_imbalance():
+    book = _book()
+    assert book.mid_price() == pytest.approx(100.25)
+    assert book.spread() == pytest.approx(0.5)
+    # (100.0 * 30 + 100.5 * 10) / 40
+    assert book.microprice() == pytest.approx(100.125)
+    assert book.imbalance() == pytest.approx((10.0 - 30.0) / 40.0)
+    assert book.imbalance(2) == pytest.approx((30.0 - 35.0) / 65.0)
+
+
+def test_order_book_depth_arrays():
+    book = _book()
+    bid_prices, bid_sizes, ask_prices, ask_sizes = book.depth(2)
+    assert isinstance(bid_prices, np.ndarray)
+    np.testing.assert_allclose(bid_prices, [100.0, 99.5])
+    np.testing.assert_allclose(bid_sizes, [10.0, 20.0])
+    np.testing.assert_allclose(ask_prices, [100.5, 101.0])
+    np.testing.assert_allclose(ask_sizes, [30.0, 5.0])
+
+    bid_prices, _, ask_prices, _ = book.depth(10)
+    assert len(bid_prices) == 3
+    assert len(ask_prices) == 2
+
+
+def test_order_book_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustOrderBook(tick_size=0.0)
+    book = RustOrderBook(tick_size=0.01)
+    with pytest.raises(ValueError):
+        book.add("middle", 100.0, 1.0)
+    with pytest.raises(ValueError):
+        book.add("bid", float("nan"), 1.0)
+    with pytest.raises(ValueError):
+        book.add("bid", 1e300, 1.0)
+    with pytest.raises(ValueError):
+        book.add("bid", 100.0, -1.0)
+    with pytest.raises(ValueError):
+        book.add("bid", 100.0, 0.0)
+    assert book.best_bid() is None
+
+    book.add("B", 100.0, 1.0)
+    book.add("sell", 100.5, 2.0)
+    assert book.best_bid() is not None
+    assert book.best_ask() is not None
+    with pytest.raises(ValueError, match="'buy'"):
+        book.add("long", 100.0, 1.0)
+
+
+def test_order_book_accepts_zero_and_negative_prices():
+    book = RustOrderBook(tick_size=0.01)
+    book.add("bid", -0.25, 4.0)
+    book.add("bid", -0.5, 1.0)
+    book.add("ask", 0.0, 2.0)
+    assert book.best_bid() == pytest.approx((-0.25, 4.0))
+    assert book.best_ask() == pytest.approx((0.0, 2.0))
+    assert book.spread() == pytest.approx(0.25)
+
+    # Off-tick prices land on, and report, the nearest tick.
+    book.add("ask", 0.0049, 1.0)
+    assert book.best_ask() == pytest.approx((0.0, 3.0))
+    book.trade("bid", -0.2501, 4.0)
+    assert book.best_bid() == pytest.approx((-0.5, 1.0))
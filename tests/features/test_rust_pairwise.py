@@ -0,0 +1,94 @@
+from __future__ import annotations
+
+import math
+import random
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustRollingCorrelation = shijim_indicators.RustRollingCorrelation
+
+
+def _pearson(xs, ys):
+    n = len(xs)
+    mx, my = sum(xs) / n, sum(ys) / n
+    sxy = sum((x - mx) * (y - my) for x, y in zip(xs, ys))
+    sxx = sum((x - mx) ** 2 for x in xs)
+    syy = sum((y - my) ** 2 for y in ys)
+    return sxy / math.sqrt(sxx * syy), sxy / sxx
+
+
+def test_count_window_correlation_and_beta():
+    xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+    ys = [2.1, 3.9, 6.2, 7.8, 10.1, 11.7]
+    corr = RustRollingCorrelation(window_size=4)
+
+    results = [corr.update(x, y) for x, y in zip(xs, ys)]
+    assert results[:3] == [None, None, None]
+    expected_corr, expected_beta = _pearson(xs[-4:], ys[-4:])
+    assert results[-1] == pytest.approx(expected_corr)
+    assert corr.beta() == pytest.approx(expected_beta)
+    mx, my = sum(xs[-4:]) / 4, sum(ys[-4:]) / 4
+    assert corr.intercept() == pytest.approx(my - expected_beta * mx)
+    assert corr.samples_ready() == 4
+
+
+def test_time_window_expires_old_pairs():
+    corr = RustRollingCorrelation(window_duration=10.0)
+    assert corr.update(1.0, -1.0, 0.0) is None
+    assert corr.update(2.0, -2.0, 1.0) == pytest.approx(-1.0)
+    corr.update(3.0, 5.0, 12.0)
+    corr.update(4.0, 6.0, 13.0)
+    # The first two pairs are now older than 10s.
+    assert corr.samples_ready() == 2
+    assert corr.correlation() == pytest.approx(1.0)
+    assert corr.beta() == pytest.approx(1.0)
+    assert corr.covariance() == pytest.approx(0.5)
+
+
+def test_rolling_correlation_validation():
+    with pytest.raises(ValueError):
+        RustRollingCorrelation()
+    with pytest.raises(ValueError):
+        RustRollingCorrelation(window_size=5, window_duration=1.0)
+    with pytest.raises(ValueError):
+        RustRollingCorrelation(window_size=1)
+
+    timed = RustRollingCorrelation(window_duration=5.0)
+    with pytest.raises(ValueError):
+        timed.update(1.0, 1.0)
+    timed.update(1.0, 1.0, 2.0)
+    with pytest.raises(ValueError):
+        timed.update(1.0, 1.0, 1.0)
+
+    flat = RustRollingCorrelation(window_size=2)
+    flat.update(1.0, 1.0)
+    assert flat.update(1.0, 2.0) is None
+    assert flat.beta() is None
+    flat.reset()
+    assert flat.samples_ready() == 0
+
+
+def test_price_level_inputs_keep_precision():
+    rng = random.Random(3)
+    corr = RustRollingCorrelation(window_size=100)
+    xs, ys = [], []
+    for _ in range(50_000):
+        x = 1e4 + rng.gauss(0.0, 1.0)
+        y = 2.0 * x + rng.gauss(0.0, 0.5)
+        xs.append(x)
+        ys.append(y)
+        corr.update(x, y)
+
+    # Two-pass reference on the final window, centred to avoid cancellation.
+    wx, wy = xs[-100:], ys[-100:]
+    mx, my = sum(wx) / 100, sum(wy) / 100
+    expected_corr, expected_beta = _pearson([x - mx for x in wx], [y - my for y in wy])
+    assert corr.beta() == pytest.approx(expected_beta, rel=1e-9)
+    assert corr.correlation() == pytest.approx(expected_corr, rel=1e-9)
+    assert corr.intercept() == pytest.approx(my - expected_beta * mx, rel=1e-6)
+
+    flat = RustRollingCorrelation(window_size=3)
+    for y in (1.0, 2.0, 3.0):
+        flat.update(2e4, y)
+    assert flat.correlation() is None
@@ -0,0 +1,166 @@
+from __future__ import annotations
+
+import math
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustPipeline = shijim_indicators.RustPipeline
+
+
+def _pipeline() -> RustPipeline:
+    pipeline = RustPipeline()
+    pipeline.add_vpin("vpin", 10.0, 1)
+    pipeline.add_ofi("ofi")
+    pipeline.add_hawkes("trade_intensity", 0.5, 0.3, 1.0)
+    pipeline.add_rolling("mid_z", 3, field="mid")
+    return pipeline
+
+
+def test_pipeline_routes_messages_by_type():
+    pipeline = _pipeline()
+    assert pipeline.names() == ["vpin", "ofi", "trade_intensity", "mid_z"]
+    assert len(pipeline) == 4
+    assert pipeline.latest() == {"vpin": None, "ofi": None, "trade_intensity": None, "mid_z": None}
+
+    pipeline.on_quote(100.0, 10.0, 101.0, 10.0, 0.0)
+    pipeline.on_quote(100.5, 5.0, 101.0, 10.0, 1.0)
+    latest = pipeline.latest()
+    assert latest["ofi"] == pytest.approx(5.0)
+    assert latest["trade_intensity"] is None
+
+    # Unsigned trades above the mid are buys under Lee-Ready.
+    pipeline.on_trade(101.0, 6.0, 2.0)
+    pipeline.on_trade(100.5, 4.0, 2.5, -1.0)
+    latest = pipeline.latest()
+    # One full bucket: 6 buys vs 4 sells.
+    assert latest["vpin"] == pytest.approx(0.2)
+    assert latest["trade_intensity"] == pytest.approx(0.5 + 0.3 * math.exp(-0.5) + 0.3)
+    # Trades leave the quote-driven nodes untouched.
+    assert latest["ofi"] == pytest.approx(5.0)
+
+    pipeline.on_quote(100.0, 5.0, 101.0, 10.0, 3.0)
+    mids = [100.5, 100.75, 100.5]
+    mean = sum(mids) / 3
+    std = math.sqrt(sum((m - mean) ** 2 for m in mids) / 2)
+    assert pipeline.latest()["mid_z"] == pytest.approx((mids[-1] - mean) / std)
+
+
+def test_pipeline_process_batch():
+    pipeline = _pipeline()
+    kinds = np.array([RustPipeline.QUOTE, RustPipeline.QUOTE, RustPipeline.TRADE], dtype=np.int64)
+    timestamps = np.array([0.0, 1.0, 2.0])
+    fields = np.array(
+        [
+            [100.0, 10.0, 101.0, 10.0],
+            [100.5, 5.0, 101.0, 10.0],
+            [101.0, 10.0, 1.0, 0.0],
+        ]
+    )
+    latest = pipeline.process(kinds, timestamps, fields)
+    assert latest["ofi"] == pytest.approx(5.0)
+    assert latest["vpin"] == pytest.approx(1.0)
+
+    with pytest.raises(ValueError):
+        pipeline.process(np.array([7], dtype=np.int64), np.array([3.0]), np.zeros((1, 4)))
+
+
+def test_pipeline_reset_and_validation():
+    pipeline = _pipeline()
+    with pytest.raises(ValueError):
+        pipeline.add_ofi("ofi")
+    with pytest.raises(ValueError):
+        pipeline.add_rolling("bad", 3, field="bid")
+    with pytest.raises(ValueError):
+        pipeline.add_hawkes("bad", 0.5, 0.3, 1.0, on="book")
+    with pytest.raises(ValueError):
+        pipeline.on_trade(100.0, 1.0, 0.0, 0.5)
+
+    pipeline.on_trade(100.0, 10.0, 1.0, 1.0)
+    assert pipeline.latest()["vpin"] == pytest.approx(1.0)
+    pipeline.reset()
+    assert pipeline.latest()["vpin"] is None
+    assert pipeline.names() == ["vpin", "ofi", "trade_intensity", "mid_z"]
+
+
+def test_pipeline_rolls_indicators_on_session_events():
+    pipeline = _pipeline()
+    assert pipeline.session_events() == [RustPipeline.EVENT_OPEN]
+
+    pipeline.on_quote(100.0, 10.0, 101.0, 10.0, 0.0)
+    pipeline.on_quote(100.5, 5.0, 101.0, 10.0, 1.0)
+    pipeline.on_trade(100.0, 10.0, 2.0, 1.0)
+    assert pipeline.latest()["vpin"] == pytest.approx(1.0)
+
+    assert pipeline.on_system_event(RustPipeline.EVENT_HALT, 3.0) is False
+    assert pipeline.latest()["ofi"] == pytest.approx(5.0)
+
+    assert pipeline.on_system_event(RustPipeline.EVENT_OPEN, 4.0) is True
+    assert pipeline.sessions_rolled() == 1
+    assert all(value is None for value in pipeline.latest().values())
+
+    # OFI has to warm up again after the boundary.
+    pipeline.on_quote(100.5, 5.0, 101.0, 10.0, 5.0)
+    assert pipeline.latest()["ofi"] is None
+    pipeline.on_trade(100.0, 10.0, 6.0)
+    assert pipeline.latest()["trade_intensity"] == pytest.approx(0.8)
+
+    with pytest.raises(ValueError):
+        pipeline.on_system_event(RustPipeline.EVENT_CLOSE, float("nan"))
+    with pytest.raises(ValueError):
+        pipeline.on_system_event(RustPipeline.EVENT_CLOSE, 5.5)
+    assert pipeline.on_system_event(RustPipeline.EVENT_CLOSE, 6.0) is False
+
+
+def test_pipeline_custom_session_events_in_batches():
+    pipeline = RustPipeline(session_events=[RustPipeline.EVENT_CLOSE])
+    pipeline.add_ofi("ofi")
+    kinds = np.array([RustPipeline.QUOTE, RustPipeline.QUOTE, RustPipeline.SYSTEM], dtype=np.int64)
+    fields = np.array(
+        [
+            [100.0, 10.0, 101.0, 10.0],
+            [100.5, 5.0, 101.0, 10.0],
+            [float(RustPipeline.EVENT_CLOSE), 0.0, 0.0, 0.0],
+        ]
+    )
+    latest = pipeline.process(kinds, np.array([0.0, 1.0, 2.0]), fields)
+    assert latest == {"ofi": None}
+    assert pipeline.sessions_rolled() == 1
+
+    with pytest.raises(ValueError):
+        pipeline.process(
+            np.array([RustPipeline.SYSTEM], dtype=np.int64),
+            np.array([3.0]),
+            np.array([[1.5, 0.0, 0.0, 0.0]]),
+        )
+
+
+def test_rejected_messages_leave_state_untouched():
+    pipeline = _pipeline()
+    pipeline.on_quote(100.0, 10.0, 101.0, 10.0, 0.0)
+    pipeline.on_trade(101.0, 6.0, 1.0)
+    before = pipeline.latest()
+
+    with pytest.raises(ValueError):
+        pipeline.on_trade(101.0, -1.0, 2.0)
+    with pytest.raises(ValueError):
+        pipeline.on_quote(100.0, 10.0, float("nan"), 10.0, 2.0)
+    with pytest.raises(ValueError):
+        pipeline.on_trade(101.0, 4.0, 0.5)
+    assert pipeline.latest() == before
+
+    # The next valid trade continues as if the rejected ones never arrived.
+    pipeline.on_trade(101.0, 4.0, 2.0)
+    assert pipeline.latest()["vpin"] == pytest.approx(1.0)
+    assert pipeline.latest()["trade_intensity"] == pytest.approx(0.5 + 0.3 * math.exp(-1.0) + 0.3)
+
+
+def test_vpin_added_after_quotes_sees_quote_history():
+    pipeline = RustPipeline()
+    pipeline.on_quote(100.0, 10.0, 101.0, 10.0, 0.0)
+    pipeline.add_vpin("vpin", 10.0, 1)
+
+    # Above the earlier mid, so the first trade is already a buy.
+    pipeline.on_trade(100.9, 10.0, 1.0)
+    assert pipeline.latest()["vpin"] == pytest.approx(1.0)
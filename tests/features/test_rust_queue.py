@@ -0,0 +1,55 @@
+from __future__ import annotations
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustQueueEstimator = shijim_indicators.RustQueueEstimator
+
+
+def test_queue_position_proportional_cancels_and_fills():
+    est = RustQueueEstimator()
+    est.place(level_depth=100.0, order_size=10.0)
+    assert est.queue_ahead() == pytest.approx(100.0)
+
+    # Depth shown includes our 10 lots: 130 visible -> 20 joined behind us.
+    est.on_depth(130.0)
+    assert est.queue_ahead() == pytest.approx(100.0)
+    assert est.position_fraction() == pytest.approx(100.0 / 120.0)
+
+    # 30 cancelled; 100/120 of it is assumed to come from ahead of us.
+    est.on_cancel(30.0)
+    assert est.queue_ahead() == pytest.approx(75.0)
+
+    # A trade of 80 clears the 75 ahead and fills 5 of ours.
+    assert est.on_trade(80.0) == pytest.approx(5.0)
+    assert est.queue_ahead() == pytest.approx(0.0)
+    assert est.remaining() == pytest.approx(5.0)
+    assert est.is_active()
+
+    assert est.on_trade(20.0) == pytest.approx(5.0)
+    assert est.filled() == pytest.approx(10.0)
+    assert not est.is_active()
+
+
+@pytest.mark.parametrize(
+    ("model", "expected_ahead"),
+    [("pessimistic", 90.0), ("optimistic", 70.0), ("proportional", 75.0)],
+)
+def test_queue_cancel_models(model: str, expected_ahead: float):
+    est = RustQueueEstimator(cancel_model=model)
+    est.place(100.0, 10.0)
+    est.on_add(20.0)
+    est.on_cancel(30.0)
+    assert est.queue_ahead() == pytest.approx(expected_ahead)
+
+
+def test_queue_estimator_validation():
+    with pytest.raises(ValueError):
+        RustQueueEstimator(cancel_model="random")
+    est = RustQueueEstimator()
+    with pytest.raises(ValueError):
+        est.place(10.0, 0.0)
+    with pytest.raises(ValueError):
+        est.on_trade(-1.0)
+    # Trades before placing an order never fill anything.
+    assert est.on_trade(5.0) == 0.0
@@ -0,0 +1,70 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustRollingStats = shijim_indicators.RustRollingStats
+
+
+def test_rolling_mean_variance_zscore():
+    stats = RustRollingStats(window_size=3, half_life=1.0)
+    assert stats.update(1.0) is None
+    assert stats.update(2.0) is None
+    assert stats.mean() is None
+
+    assert stats.update(3.0) == pytest.approx(1.0)
+    assert stats.mean() == pytest.approx(2.0)
+    assert stats.variance() == pytest.approx(1.0)
+
+    # Window slides to [2, 3, 4].
+    assert stats.update(4.0) == pytest.approx(1.0)
+    assert stats.mean() == pytest.approx(3.0)
+    assert stats.min() == pytest.approx(2.0)
+    assert stats.max() == pytest.approx(4.0)
+    assert stats.zscore(1.0) == pytest.approx(-2.0)
+
+
+def test_rolling_min_max_expire_with_window():
+    stats = RustRollingStats(window_size=3)
+    for value in (5.0, 1.0, 4.0, 3.0, 2.0):
+        stats.update(value)
+    # Window is [4, 3, 2]: the 5 and the 1 have both expired.
+    assert stats.min() == pytest.approx(2.0)
+    assert stats.max() == pytest.approx(4.0)
+
+
+def test_ewma_half_life():
+    stats = RustRollingStats(window_size=10, half_life=1.0)
+    assert stats.ewma_alpha() == pytest.approx(0.5)
+    for value in (1.0, 2.0, 3.0, 4.0):
+        stats.update(value)
+    assert stats.ewma() == pytest.approx(3.125)
+    assert stats.ewm_std() > 0.0
+
+    stats.reset()
+    assert stats.ewma() is None
+    assert stats.samples_ready() == 0
+
+
+def test_rolling_batch_matches_numpy():
+    values = np.asarray([3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0], dtype=np.float64)
+    stats = RustRollingStats(window_size=4)
+    zscores = stats.update_many(values)
+    for end in range(4, len(values) + 1):
+        window = values[end - 4 : end]
+        expected = (window[-1] - window.mean()) / window.std(ddof=1)
+        assert zscores[end - 1] == pytest.approx(expected)
+
+
+def test_rolling_stats_validation():
+    with pytest.raises(ValueError):
+        RustRollingStats(window_size=1)
+    with pytest.raises(ValueError):
+        RustRollingStats(window_size=5, half_life=0.0)
+    stats = RustRollingStats(window_size=2)
+    with pytest.raises(ValueError):
+        stats.update(float("inf"))
+    # Constant window has no z-score.
+    stats.update(1.0)
+    assert stats.update(1.0) is None
@@ -0,0 +1,70 @@
+from __future__ import annotations
+
+import math
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustRealizedVolatility = shijim_indicators.RustRealizedVolatility
+
+
+def test_realized_variance_and_bipower_variation():
+    calc = RustRealizedVolatility(window_size=3)
+    assert calc.update(100.0, 0.0) is None
+    assert calc.update(101.0, 1.0) is None
+    assert calc.update(100.0, 2.0) is None
+
+    r1 = math.log(101.0 / 100.0)
+    r2 = math.log(100.0 / 101.0)
+    r3 = math.log(102.0 / 100.0)
+    rv = calc.update(102.0, 3.0)
+    assert rv == pytest.approx(r1**2 + r2**2 + r3**2)
+    assert calc.realized_volatility() == pytest.approx(math.sqrt(rv))
+    assert calc.bipower_variation() == pytest.approx(
+        math.pi / 2 * (abs(r2) * abs(r1) + abs(r3) * abs(r2))
+    )
+    # Returns stamped at t=1..3 span two time units.
+    assert calc.variance_rate() == pytest.approx(rv / 2.0)
+
+    # The oldest return rolls out of the window.
+    r4 = math.log(101.0 / 102.0)
+    assert calc.update(101.0, 4.0) == pytest.approx(r2**2 + r3**2 + r4**2)
+
+
+def test_realized_variance_subsampling():
+    prices = [100.0, 100.5, 100.0, 100.5, 101.0]
+    calc = RustRealizedVolatility(window_size=3, subsample_step=2)
+    results = [calc.update(p, float(i)) for i, p in enumerate(prices)]
+    assert results[:4] == [None, None, None, None]
+
+    r02 = math.log(prices[2] / prices[0])
+    r13 = math.log(prices[3] / prices[1])
+    r24 = math.log(prices[4] / prices[2])
+    assert results[4] == pytest.approx((r02**2 + r13**2 + r24**2) / 2.0)
+    assert calc.bipower_variation() == pytest.approx(
+        math.pi / 2 * abs(r24) * abs(r02) / 2.0
+    )
+
+
+def test_realized_variance_batch_matches_sequential():
+    prices = np.asarray([100.0, 100.2, 99.9, 100.4, 100.1, 100.3], dtype=np.float64)
+    ts = np.arange(len(prices), dtype=np.float64)
+    batch = RustRealizedVolatility(window_size=4).update_many(prices, ts)
+    calc = RustRealizedVolatility(window_size=4)
+    sequential = [calc.update(float(p), float(t)) for p, t in zip(prices, ts)]
+    assert batch == sequential
+
+
+def test_realized_variance_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustRealizedVolatility(window_size=2, subsample_step=2)
+    with pytest.raises(ValueError):
+        RustRealizedVolatility(window_size=5, subsample_step=0)
+
+    calc = RustRealizedVolatility(window_size=3)
+    with pytest.raises(ValueError):
+        calc.update(-1.0, 0.0)
+    calc.update(100.0, 5.0)
+    with pytest.raises(ValueError):
+        calc.update(100.0, 4.0)
@@ -0,0 +1,77 @@
+from __future__ import annotations
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustSpreadMetrics = shijim_indicators.RustSpreadMetrics
+
+
+def test_effective_and_realized_spreads():
+    sm = RustSpreadMetrics(window_size=10, markout_horizon=5.0)
+    assert sm.update_trade(100.0, 1.0, 0.0) is None  # no quote yet
+
+    sm.update_quote(100.0, 101.0, 0.0)
+    assert sm.update_trade(101.0, 1.0, 1.0) == pytest.approx(1.0)
+    # Sign inferred from the midpoint: 100 < 100.5 is a sell.
+    assert sm.update_trade(100.0, 0.0, 2.0) == pytest.approx(1.0)
+    assert sm.pending_markouts() == 2
+
+    assert sm.update_quote(101.0, 102.0, 4.0) == 0
+    # The buy's markout (t=6) is priced off the mid prevailing then: 101.5.
+    assert sm.update_quote(102.0, 103.0, 6.5) == 1
+    assert sm.last_realized_spread() == pytest.approx(2.0 * (101.0 - 101.5))
+    # The sell's markout (t=7) coincides with a quote and uses it: mid 102.5.
+    assert sm.update_quote(102.0, 103.0, 7.0) == 1
+    assert sm.last_realized_spread() == pytest.approx(-2.0 * (100.0 - 102.5))
+
+    assert sm.quoted_spread() == pytest.approx(1.0)
+    assert sm.effective_spread() == pytest.approx(1.0)
+    assert sm.realized_spread() == pytest.approx(2.0)
+    assert sm.price_impact() == pytest.approx(-1.0)
+
+
+def test_relative_spreads_and_window():
+    sm = RustSpreadMetrics(window_size=2, markout_horizon=0.0, relative=True)
+    sm.update_quote(99.0, 101.0, 0.0)
+    sm.update_quote(99.5, 100.5, 1.0)
+    sm.update_quote(99.75, 100.25, 2.0)
+    # Only the last two quotes are in the window.
+    assert sm.quoted_spread() == pytest.approx((1.0 + 0.5) / 2 / 100.0)
+
+    # Zero horizon: realized equals effective.
+    effective = sm.update_trade(100.25, 1.0, 3.0)
+    assert effective == pytest.approx(0.5 / 100.0)
+    assert sm.last_realized_spread() == pytest.approx(effective)
+
+
+def test_spread_metrics_validation():
+    with pytest.raises(ValueError):
+        RustSpreadMetrics(window_size=0, markout_horizon=1.0)
+    sm = RustSpreadMetrics(window_size=5, markout_horizon=1.0)
+    with pytest.raises(ValueError):
+        sm.update_quote(float("nan"), 100.0, 0.0)
+    sm.update_quote(100.0, 101.0, 5.0)
+    with pytest.raises(ValueError):
+        sm.update_trade(100.5, 2.0, 6.0)
+    with pytest.raises(ValueError):
+        sm.update_trade(100.5, 1.0, 4.0)
+
+
+def test_crossed_quotes_are_skipped_and_counted():
+    sm = RustSpreadMetrics(window_size=5, markout_horizon=1.0)
+    assert sm.update_quote(101.0, 100.0, 0.0) is None
+    assert sm.quoted_spread() is None
+    assert sm.update_trade(100.5, 1.0, 0.5) is None  # still no mid
+
+    sm.update_quote(100.0, 101.0, 1.0)
+    sm.update_trade(101.0, 1.0, 2.0)
+    # A crossed quote past the deadline neither moves the mid nor resolves.
+    assert sm.update_quote(100.8, 100.6, 3.5) is None
+    assert sm.pending_markouts() == 1
+    assert sm.quoted_spread() == pytest.approx(1.0)
+    assert sm.crossed_quotes() == 2
+
+    assert sm.update_quote(100.0, 101.0, 4.0) == 1
+    assert sm.last_realized_spread() == pytest.approx(1.0)
+    sm.reset()
+    assert sm.crossed_quotes() == 0
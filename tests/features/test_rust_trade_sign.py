@@ -0,0 +1,87 @@
+from __future__ import annotations
+
+import numpy as np
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustTradeClassifier = shijim_indicators.RustTradeClassifier
+RustVpinCalculator = shijim_indicators.RustVpinCalculator
+
+
+def test_lee_ready_quote_rule_with_tick_fallback():
+    clf = RustTradeClassifier()
+    clf.update_quote(100.0, 101.0, 0.0)
+
+    assert clf.classify(100.8, 5.0, 1.0) == pytest.approx(5.0)  # above mid
+    assert clf.classify(100.1, 3.0, 2.0) == pytest.approx(-3.0)  # below mid
+    # At the midpoint the tick rule decides: 100.5 > 100.1 is an uptick.
+    assert clf.classify(100.5, 2.0, 3.0) == pytest.approx(2.0)
+    assert clf.last_sign() == 1.0
+
+
+def test_lee_ready_quote_lag_uses_prevailing_quote():
+    clf = RustTradeClassifier(quote_lag=1.0)
+    clf.update_quote(100.0, 101.0, 0.0)
+    clf.update_quote(102.0, 103.0, 4.5)
+
+    # At t=5 the quote from t=4.5 is too recent; the t=0 midpoint (100.5) applies.
+    assert clf.classify(101.0, 1.0, 5.0) == pytest.approx(1.0)
+    # At t=6 the newer quote (mid 102.5) prevails.
+    assert clf.classify(101.0, 1.0, 6.0) == pytest.approx(-1.0)
+
+
+def test_tick_rule_and_vpin_integration():
+    clf = RustTradeClassifier(method="tick")
+    # First trade has no reference price; zero ticks inherit the previous sign.
+    signed = [
+        clf.classify(p, v, float(t))
+        for t, (p, v) in enumerate([(10.0, 5.0), (10.1, 5.0), (10.1, 5.0), (10.0, 5.0)])
+    ]
+    assert signed == pytest.approx([0.0, 5.0, 5.0, -5.0])
+
+    vpin = RustVpinCalculator(bucket_volume=10.0, window_size=1)
+    results = [vpin.update_signed_volume(v) for v in signed]
+    # Unclassified volume is skipped; the two upticks fill one all-buy bucket.
+    assert results[:2] == [None, None]
+    assert results[2] == pytest.approx(1.0)
+
+
+def test_classify_many_batch():
+    prices = np.asarray([100.8, 100.1, 100.5, 100.5], dtype=np.float64)
+    volumes = np.asarray([1.0, 2.0, 3.0, 4.0], dtype=np.float64)
+    ts = np.asarray([1.0, 2.0, 3.0, 4.0], dtype=np.float64)
+    batch_clf = RustTradeClassifier()
+    batch_clf.update_quote(100.0, 101.0, 0.0)
+    batch = batch_clf.classify_many(prices, volumes, ts)
+    assert batch == pytest.approx([1.0, -2.0, 3.0, 4.0])
+
+
+def test_trade_classifier_validation():
+    with pytest.raises(ValueError):
+        RustTradeClassifier(method="emo")
+    with pytest.raises(ValueError):
+        RustTradeClassifier(quote_lag=-1.0)
+    clf = RustTradeClassifier()
+    clf.update_quote(1.0, 2.0, 5.0)
+    with pytest.raises(ValueError):
+        clf.update_quote(1.0, 2.0, 4.0)
+    with pytest.raises(ValueError):
+        clf.classify(1.5, -1.0, 6.0)
+
+
+def test_quote_buffer_stays_bounded_without_trades():
+    clf = RustTradeClassifier()
+    for i in range(10_000):
+        clf.update_quote(100.0, 101.0, i * 0.01)
+    assert clf.buffered_quotes() == 1
+
+    lagged = RustTradeClassifier(quote_lag=1.0)
+    for i in range(10_000):
+        lagged.update_quote(100.0, 101.0, i * 0.01)
+    # Only the quotes within the last second plus the one prevailing before it.
+    assert lagged.buffered_quotes() <= 102
+
+    tick = RustTradeClassifier(method="tick")
+    for i in range(100):
+        tick.update_quote(100.0, 101.0, float(i))
+    assert tick.buffered_quotes() == 0
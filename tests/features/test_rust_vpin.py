@@ -1,5 +1,7 @@
 from __future__ import annotations
 
+import math
+
 import numpy as np
 import pytest
 
@@ -36,3 +38,57 @@ def test_vpin_reset_and_invalid_inputs():
 
     with pytest.raises(ValueError):
         calc.update_signed_volume(float("nan"))
+
+
+def test_vpin_window_size_upper_bound():
+    with pytest.raises(ValueError):
+        RustVpinCalculator(bucket_volume=100.0, window_size=10**12)
+
+    # The bound is configurable for callers that really need long windows.
+    with pytest.raises(ValueError):
+        RustVpinCalculator(bucket_volume=100.0, window_size=20, max_window_size=10)
+    calc = RustVpinCalculator(bucket_volume=100.0, window_size=2_000_000, max_window_size=2_000_000)
+    assert calc.update_signed_volume(100.0) is None
+
+    calc = RustVpinCalculator(bucket_volume=100.0, window_size=2)
+    assert calc.update_signed_volume(100.0) is None
+    assert calc.update_signed_volume(-100.0) == pytest.approx(1.0)
+    assert calc.buckets_ready() == 2
+
+
+def test_vpin_bulk_volume_classification():
+    calc = RustVpinCalculator(bucket_volume=100.0, window_size=1, bulk_classification=True)
+    assert calc.uses_bulk_classification()
+
+    # No price move: volume splits evenly, no imbalance.
+    assert calc.update_bulk(100.0, 0.0, 1.0) == pytest.approx(0.0, abs=1e-6)
+
+    # One-sigma up move: buy share is Phi(1) ~= 0.841345.
+    buy_share = 0.5 * (1.0 + math.erf(1.0 / math.sqrt(2.0)))
+    assert calc.update_bulk(100.0, 0.5, 0.5) == pytest.approx(2.0 * buy_share - 1.0, abs=1e-6)
+
+    # A bar spanning two buckets is split proportionally across both.
+    calc = RustVpinCalculator(bucket_volume=50.0, window_size=2, bulk_classification=True)
+    assert calc.update_bulk(100.0, -1.0, 1.0) == pytest.approx(2.0 * buy_share - 1.0, abs=1e-6)
+    assert calc.buckets_ready() == 2
+
+
+def test_vpin_classification_modes_are_exclusive():
+    signed = RustVpinCalculator(bucket_volume=100.0, window_size=1)
+    with pytest.raises(ValueError):
+        signed.update_bulk(10.0, 0.1, 1.0)
+
+    bulk = RustVpinCalculator(bucket_volume=100.0, window_size=1, bulk_classification=True)
+    with pytest.raises(ValueError):
+        bulk.update_signed_volume(10.0)
+    with pytest.raises(ValueError):
+        bulk.update_bulk(10.0, 0.1, 0.0)
+
+
+def test_vpin_bulk_series_np_uses_nan_for_not_ready():
+    bulk = RustVpinCalculator(bucket_volume=10.0, window_size=1, bulk_classification=True)
+    out = bulk.update_bulk_series_np(
+        np.asarray([5.0, 5.0]), np.asarray([0.0, 0.0]), np.asarray([1.0, 1.0])
+    )
+    assert np.isnan(out[0])
+    assert out[1] == pytest.approx(0.0, abs=1e-6)
@@ -0,0 +1,93 @@
+from __future__ import annotations
+
+import pytest
+
+shijim_indicators = pytest.importorskip("shijim_indicators")
+RustVwapCalculator = shijim_indicators.RustVwapCalculator
+
+
+def test_session_vwap_and_twap():
+    calc = RustVwapCalculator(60.0)
+
+    assert calc.update(100.0, 10.0, 0.0) == pytest.approx(100.0)
+    assert calc.update(102.0, 30.0, 10.0) == pytest.approx((1000.0 + 3060.0) / 40.0)
+    calc.update(101.0, 10.0, 40.0)
+
+    assert calc.session_vwap() == pytest.approx((1000.0 + 3060.0 + 1010.0) / 50.0)
+    assert calc.session_volume() == pytest.approx(50.0)
+    # 100 held for 10s, 102 held for 30s.
+    assert calc.session_twap() == pytest.approx((100.0 * 10.0 + 102.0 * 30.0) / 40.0)
+
+
+def test_rolling_window_uses_carried_price():
+    calc = RustVwapCalculator(10.0)
+
+    calc.update(100.0, 5.0, 0.0)
+    calc.update(110.0, 5.0, 5.0)
+    calc.update(120.0, 10.0, 20.0)
+
+    # Only the last trade is inside [10, 20].
+    assert calc.rolling_vwap() == pytest.approx(120.0)
+    # 110 prevailed from the window start until the trade at 20.
+    assert calc.rolling_twap() == pytest.approx(110.0)
+    # The session keeps everything.
+    assert calc.session_vwap() == pytest.approx((500.0 + 550.0 + 1200.0) / 20.0)
+
+
+def test_reset_session_keeps_rolling_window():
+    calc = RustVwapCalculator(100.0)
+    calc.update(100.0, 10.0, 0.0)
+    calc.update(104.0, 10.0, 1.0)
+
+    calc.reset_session()
+    assert calc.session_vwap() is None
+    assert calc.session_twap() is None
+    assert calc.rolling_vwap() == pytest.approx(102.0)
+
+    assert calc.update(106.0, 10.0, 2.0) == pytest.approx(106.0)
+    assert calc.rolling_vwap() == pytest.approx((1000.0 + 1040.0 + 1060.0) / 30.0)
+
+
+def test_anchored_vwap_ignores_trades_before_anchor():
+    calc = RustVwapCalculator(100.0, anchor_ts=10.0)
+
+    assert calc.update(100.0, 10.0, 5.0) is None
+    assert calc.update(200.0, 1.0, 15.0) == pytest.approx(200.0)
+    assert calc.anchor_ts() == pytest.approx(10.0)
+    calc.update(210.0, 1.0, 20.0)
+    # From the anchor: 100 prevailed for 5s, then 200 for 5s.
+    assert calc.session_twap() == pytest.approx(150.0)
+
+    calc.reset_session(30.0)
+    assert calc.update(220.0, 1.0, 25.0) is None
+    assert calc.update(230.0, 3.0, 35.0) == pytest.approx(230.0)
+    with pytest.raises(ValueError):
+        calc.reset_session(0.0)
+
+
+def test_vwap_invalid_inputs():
+    with pytest.raises(ValueError):
+        RustVwapCalculator(0.0)
+    calc = RustVwapCalculator(10.0)
+    with pytest.raises(ValueError):
+        calc.update(-1.0, 1.0, 0.0)
+    calc.update(100.0, 1.0, 5.0)
+    with pytest.raises(ValueError):
+        calc.update(100.0, 1.0, 4.0)
+
+    calc.reset()
+    assert calc.rolling_vwap() is None
+    assert calc.rolling_twap() is None
+
+
+def test_reset_restores_configured_anchor():
+    calc = RustVwapCalculator(100.0, anchor_ts=10.0)
+    calc.update(100.0, 1.0, 12.0)
+    calc.reset_session(30.0)
+    assert calc.anchor_ts() == pytest.approx(30.0)
+
+    calc.reset()
+    # Re-anchoring only lasts until the next reset.
+    assert calc.anchor_ts() == pytest.approx(10.0)
+    assert calc.update(100.0, 1.0, 5.0) is None
+    assert calc.update(120.0, 1.0, 11.0) == pytest.approx(120.0)